@@ -0,0 +1,253 @@
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+use core::{cmp, io::BorrowedBuf, mem::MaybeUninit};
+
+#[cfg(feature = "alloc")]
+use crate::Error;
+use crate::{Read, Result};
+
+/// The buffer shared by [`BufReader`] and [`ReadAheadReader`]: bytes read
+/// ahead from a reader, stored in `S`.
+///
+/// `buf[pos..filled]` holds the data that has not been consumed yet, and
+/// `pos <= filled <= initialized <= capacity` always holds.
+///
+/// [`BufReader`]: super::BufReader
+/// [`ReadAheadReader`]: super::ReadAheadReader
+pub(crate) struct Buffer<S> {
+    buf: S,
+    pos: usize,
+    filled: usize,
+    // Each call to `refill` sets `filled` to indicate how many bytes at the start of `buf` are
+    // initialized with bytes from a read.
+    #[cfg(borrowedbuf_init)]
+    // This is the max number of bytes returned across all `refill` calls. We track this so that
+    // we can accurately tell `read_buf` how many bytes of buf are initialized, to bypass as much
+    // of its defensive initialization as possible.
+    initialized: usize,
+}
+
+impl<S> Buffer<S> {
+    pub const fn new(buf: S) -> Buffer<S> {
+        Buffer {
+            buf,
+            pos: 0,
+            filled: 0,
+            #[cfg(borrowedbuf_init)]
+            initialized: 0,
+        }
+    }
+
+    /// Returns the number of unconsumed bytes.
+    pub const fn len(&self) -> usize {
+        self.filled - self.pos
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.pos >= self.filled
+    }
+
+    pub fn discard_buffer(&mut self) {
+        self.pos = 0;
+        self.filled = 0;
+    }
+
+    pub fn consume(&mut self, amt: usize) {
+        #[cfg(feature = "no-panic")]
+        let amt = cmp::min(amt, self.filled - self.pos);
+        self.pos = cmp::min(self.pos + amt, self.filled);
+    }
+
+    /// Moves the read position by `offset` if it stays within the buffered
+    /// data. Returns `false` without moving otherwise.
+    #[cfg(feature = "alloc")]
+    pub fn seek_relative(&mut self, offset: i64) -> bool {
+        let pos = self.pos as u64;
+        if offset < 0 {
+            if let Some(new_pos) = pos.checked_sub(offset.unsigned_abs()) {
+                self.pos = new_pos as usize;
+                return true;
+            }
+        } else if let Some(new_pos) = pos.checked_add(offset as u64)
+            && new_pos <= self.filled as u64
+        {
+            self.pos = new_pos as usize;
+            return true;
+        }
+        false
+    }
+
+    /// Adds the buffered bytes to a reader's size hint.
+    pub fn size_hint(&self, (lower, upper): (usize, Option<usize>)) -> (usize, Option<usize>) {
+        let buffered = self.len();
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|u| u.checked_add(buffered)),
+        )
+    }
+}
+
+impl<S: AsRef<[MaybeUninit<u8>]> + AsMut<[MaybeUninit<u8>]>> Buffer<S> {
+    pub fn capacity(&self) -> usize {
+        self.buf.as_ref().len()
+    }
+
+    pub fn buffer(&self) -> &[u8] {
+        // SAFETY: self.pos and self.filled are valid, and self.filled >= self.pos, and
+        // that region is initialized because those are all invariants of this type.
+        unsafe {
+            self.buf
+                .as_ref()
+                .get_unchecked(self.pos..self.filled)
+                .assume_init_ref()
+        }
+    }
+
+    /// Calls `visitor` with the next `amt` bytes and consumes them, if they
+    /// are all buffered. Returns `false` without calling it otherwise.
+    pub fn consume_with(&mut self, amt: usize, visitor: impl FnOnce(&[u8])) -> bool {
+        if let Some(claimed) = self.buffer().get(..amt) {
+            visitor(claimed);
+            // If the indexing into self.buffer() succeeds, amt must be a valid increment.
+            self.pos += amt;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Remove bytes that have already been read from the buffer.
+    pub fn backshift(&mut self) {
+        if self.pos == 0 {
+            return;
+        }
+        #[cfg(feature = "no-panic")]
+        {
+            self.filled = cmp::min(self.filled, self.capacity());
+            self.pos = cmp::min(self.pos, self.filled);
+        }
+        self.buf.as_mut().copy_within(self.pos..self.filled, 0);
+        self.filled -= self.pos;
+        self.pos = 0;
+    }
+
+    /// Read more bytes into the buffer without discarding any of its contents
+    pub fn read_more(&mut self, reader: &mut (impl Read + ?Sized)) -> Result<usize> {
+        #[cfg(feature = "no-panic")]
+        {
+            self.filled = cmp::min(self.filled, self.capacity());
+        }
+        let mut buf = BorrowedBuf::from(&mut self.buf.as_mut()[self.filled..]);
+        #[cfg(borrowedbuf_init)]
+        let old_init = self.initialized - self.filled;
+        #[cfg(borrowedbuf_init)]
+        // SAFETY: `old_init` bytes past `filled` have been initialized by a
+        // previous read.
+        unsafe {
+            buf.set_init(old_init);
+        }
+        reader.read_buf(buf.unfilled())?;
+        self.filled += buf.len();
+        #[cfg(borrowedbuf_init)]
+        {
+            self.initialized += buf.init_len() - old_init;
+        }
+        Ok(buf.len())
+    }
+
+    /// Replaces the buffered data with a single read of at most `len` bytes
+    /// from `reader`.
+    pub fn refill(&mut self, reader: &mut (impl Read + ?Sized), len: usize) -> Result<()> {
+        let len = cmp::min(len, self.capacity());
+        let mut buf = BorrowedBuf::from(&mut self.buf.as_mut()[..len]);
+        #[cfg(borrowedbuf_init)]
+        // SAFETY: `self.initialized` bytes will always have been initialized.
+        unsafe {
+            buf.set_init(cmp::min(self.initialized, len));
+        }
+
+        let result = reader.read_buf(buf.unfilled());
+
+        self.pos = 0;
+        self.filled = buf.len();
+        #[cfg(borrowedbuf_init)]
+        {
+            self.initialized = cmp::max(self.initialized, buf.init_len());
+        }
+
+        result
+    }
+
+    /// Moves the buffered data to the end of `buf`, then reads the rest of
+    /// `reader` into it.
+    #[cfg(feature = "alloc")]
+    pub fn read_to_end(
+        &mut self,
+        reader: &mut (impl Read + ?Sized),
+        buf: &mut Vec<u8>,
+    ) -> Result<usize> {
+        // Reserve for the reader too so that the data is collected in one
+        // allocation whenever its size is known.
+        let (lower, _) = self.size_hint(reader.size_hint());
+        buf.try_reserve(lower).map_err(|_| Error::NoMemory)?;
+        let inner_buf = self.buffer();
+        buf.extend_from_slice(inner_buf);
+        let nread = inner_buf.len();
+        self.discard_buffer();
+        Ok(nread + reader.read_to_end(buf)?)
+    }
+
+    /// Moves the buffered data to the end of `buf`, then reads the rest of
+    /// `reader` into it, checking that everything appended is valid UTF-8.
+    #[cfg(feature = "alloc")]
+    pub fn read_to_string(
+        &mut self,
+        reader: &mut (impl Read + ?Sized),
+        buf: &mut String,
+    ) -> Result<usize> {
+        // In the general `else` case below we must read bytes into a side buffer, check
+        // that they are valid UTF-8, and then append them to `buf`. This requires a
+        // potentially large memcpy.
+        //
+        // If `buf` is empty--the most common case--we can leverage `append_to_string`
+        // to read directly into `buf`'s internal byte buffer, saving an allocation and
+        // a memcpy.
+        if buf.is_empty() {
+            // `append_to_string`'s safety relies on the buffer only being appended to since
+            // it only checks the UTF-8 validity of new data. If there were existing content in
+            // `buf` then an untrustworthy reader could not only append bytes but also modify
+            // existing bytes and render them invalid. On the other hand, if `buf` is empty
+            // then by definition any writes must be appends and `append_to_string` will
+            // validate all of the new bytes.
+            unsafe { crate::append_to_string(buf, |b| self.read_to_end(reader, b)) }
+        } else {
+            // We cannot append our byte buffer directly onto the `buf` String as there could
+            // be an incomplete UTF-8 sequence that has only been partially read. We must read
+            // everything into a side buffer first and then call `from_utf8` on the complete
+            // buffer.
+            let mut bytes = Vec::new();
+            self.read_to_end(reader, &mut bytes)?;
+            let string = core::str::from_utf8(&bytes).map_err(|_| {
+                axerrno::ax_err_type!(InvalidData, "stream did not contain valid UTF-8")
+            })?;
+            buf.try_reserve(string.len()).map_err(|_| Error::NoMemory)?;
+            *buf += string;
+            Ok(string.len())
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Buffer<Vec<MaybeUninit<u8>>> {
+    /// Grows the storage to `capacity` bytes, if it is smaller.
+    pub fn try_grow(&mut self, capacity: usize) -> Result<()> {
+        let len = self.buf.len();
+        if capacity > len {
+            self.buf
+                .try_reserve_exact(capacity - len)
+                .map_err(|_| Error::NoMemory)?;
+            self.buf.resize(capacity, MaybeUninit::uninit());
+        }
+        Ok(())
+    }
+}
@@ -1,24 +1,50 @@
 #[cfg(feature = "alloc")]
 use alloc::{string::String, vec::Vec};
+use core::{io::BorrowedCursor, mem::MaybeUninit};
 
-use crate::{BufRead, DEFAULT_BUF_SIZE, Read, Result};
+use super::buffer::Buffer;
+use crate::{BufRead, DEFAULT_BUF_SIZE, PartialTransfer, Read, Result};
 
 /// The `BufReader<R>` struct adds buffering to any reader.
 pub struct BufReader<R> {
     inner: R,
-    pos: usize,
-    filled: usize,
-    buf: [u8; DEFAULT_BUF_SIZE],
+    buf: Buffer<[MaybeUninit<u8>; DEFAULT_BUF_SIZE]>,
 }
 
 impl<R: Read> BufReader<R> {
+    /// Attempt to look ahead `n` bytes.
+    ///
+    /// `n` must be less than or equal to `capacity`, otherwise
+    /// [`Error::InvalidInput`] is returned.
+    ///
+    /// The returned slice may be less than `n` bytes long if
+    /// end of file is reached.
+    ///
+    /// After calling this method, you may call [`consume`](BufRead::consume)
+    /// with a value less than or equal to `n` to advance over some or all of
+    /// the returned bytes.
+    ///
+    /// [`Error::InvalidInput`]: crate::Error::InvalidInput
+    pub fn peek(&mut self, n: usize) -> Result<&[u8]> {
+        if n > self.capacity() {
+            return Err(crate::Error::InvalidInput);
+        }
+        while n > self.buf.len() {
+            self.buf.backshift();
+            let new = self.buf.read_more(&mut self.inner)?;
+            if new == 0 {
+                // end of file, no more bytes to read
+                return Ok(self.buffer());
+            }
+        }
+        Ok(&self.buffer()[..n])
+    }
+
     /// Creates a new `BufReader<R>` with a default buffer capacity (1 KB).
     pub const fn new(inner: R) -> BufReader<R> {
         Self {
             inner,
-            pos: 0,
-            filled: 0,
-            buf: [0; DEFAULT_BUF_SIZE],
+            buf: Buffer::new([MaybeUninit::uninit(); DEFAULT_BUF_SIZE]),
         }
     }
 }
@@ -40,7 +66,7 @@ impl<R> BufReader<R> {
     ///
     /// [`fill_buf`]: BufRead::fill_buf
     pub fn buffer(&self) -> &[u8] {
-        &self.buf[self.pos..self.filled]
+        self.buf.buffer()
     }
 
     /// Returns the number of bytes the internal buffer can hold at once.
@@ -52,15 +78,6 @@ impl<R> BufReader<R> {
     pub fn into_inner(self) -> R {
         self.inner
     }
-
-    fn discard_buffer(&mut self) {
-        self.pos = 0;
-        self.filled = 0;
-    }
-
-    const fn is_empty(&self) -> bool {
-        self.pos >= self.filled
-    }
 }
 
 impl<R: Read> Read for BufReader<R> {
@@ -68,8 +85,8 @@ impl<R: Read> Read for BufReader<R> {
         // If we don't have any buffered data and we're doing a massive read
         // (larger than our internal buffer), bypass our internal buffer
         // entirely.
-        if self.is_empty() && buf.len() >= self.capacity() {
-            self.discard_buffer();
+        if self.buf.is_empty() && buf.len() >= self.capacity() {
+            self.buf.discard_buffer();
             return self.inner.read(buf);
         }
         let nread = {
@@ -80,77 +97,101 @@ impl<R: Read> Read for BufReader<R> {
         Ok(nread)
     }
 
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        // If we don't have any buffered data and we're doing a massive read
+        // (larger than our internal buffer), bypass our internal buffer
+        // entirely.
+        if self.buf.is_empty() && cursor.capacity() >= self.capacity() {
+            self.buf.discard_buffer();
+            return self.inner.read_buf(cursor);
+        }
+
+        let prev = cursor.written();
+
+        let mut rem = self.fill_buf()?;
+        rem.read_buf(cursor.reborrow())?; // actually never fails
+
+        self.consume(cursor.written() - prev); // slice impl of read_buf known to never unfill buf
+
+        Ok(())
+    }
+
+    fn read_buf_exact(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        if self
+            .buf
+            .consume_with(cursor.capacity(), |claimed| cursor.append(claimed))
+        {
+            return Ok(());
+        }
+        crate::default_read_buf_exact(self, cursor)
+    }
+
     // Small read_exacts from a BufReader are extremely common when used with a deserializer.
     // The default implementation calls read in a loop, which results in surprisingly poor code
     // generation for the common path where the buffer has enough bytes to fill the passed-in
     // buffer.
     fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
-        let amt = buf.len();
-        if let Some(claimed) = self.buffer().get(..amt) {
-            buf.copy_from_slice(claimed);
-            self.pos += amt;
+        if self
+            .buf
+            .consume_with(buf.len(), |claimed| buf.copy_from_slice(claimed))
+        {
             return Ok(());
         }
-        self.inner.read_exact(buf)
+        crate::default_read_exact(self, buf)
+    }
+
+    fn read_exact_counted(&mut self, buf: &mut [u8]) -> core::result::Result<(), PartialTransfer> {
+        if self
+            .buf
+            .consume_with(buf.len(), |claimed| buf.copy_from_slice(claimed))
+        {
+            return Ok(());
+        }
+        crate::default_read_exact_counted(self, buf)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.buf.size_hint(self.inner.size_hint())
+    }
+
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        crate::default_buf_read_to_end_into(self, buf)
     }
 
     // The inner reader might have an optimized `read_to_end`. Drain our buffer and then
     // delegate to the inner implementation.
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
-        let inner_buf = self.buffer();
-        buf.extend_from_slice(inner_buf);
-        let nread = inner_buf.len();
-        self.discard_buffer();
-        Ok(nread + self.inner.read_to_end(buf)?)
+        self.buf.read_to_end(&mut self.inner, buf)
     }
 
     // The inner reader might have an optimized `read_to_end`. Drain our buffer and then
     // delegate to the inner implementation.
     #[cfg(feature = "alloc")]
     fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
-        // In the general `else` case below we must read bytes into a side buffer, check
-        // that they are valid UTF-8, and then append them to `buf`. This requires a
-        // potentially large memcpy.
-        //
-        // If `buf` is empty--the most common case--we can leverage `append_to_string`
-        // to read directly into `buf`'s internal byte buffer, saving an allocation and
-        // a memcpy.
-        if buf.is_empty() {
-            // `append_to_string`'s safety relies on the buffer only being appended to since
-            // it only checks the UTF-8 validity of new data. If there were existing content in
-            // `buf` then an untrustworthy reader (i.e. `self.inner`) could not only append
-            // bytes but also modify existing bytes and render them invalid. On the other hand,
-            // if `buf` is empty then by definition any writes must be appends and
-            // `append_to_string` will validate all of the new bytes.
-            unsafe { crate::append_to_string(buf, |b| self.read_to_end(b)) }
-        } else {
-            // We cannot append our byte buffer directly onto the `buf` String as there could
-            // be an incomplete UTF-8 sequence that has only been partially read. We must read
-            // everything into a side buffer first and then call `from_utf8` on the complete
-            // buffer.
-            let mut bytes = Vec::new();
-            self.read_to_end(&mut bytes)?;
-            let string = core::str::from_utf8(&bytes).map_err(|_| {
-                axerrno::ax_err_type!(InvalidData, "stream did not contain valid UTF-8")
-            })?;
-            *buf += string;
-            Ok(string.len())
-        }
+        self.buf.read_to_string(&mut self.inner, buf)
     }
 }
 
 impl<R: Read> BufRead for BufReader<R> {
     fn fill_buf(&mut self) -> Result<&[u8]> {
-        if self.is_empty() {
-            let read_len = self.inner.read(&mut self.buf)?;
-            self.pos = 0;
-            self.filled = read_len;
+        // If we've reached the end of our internal buffer then we need to fetch
+        // some more data from the reader.
+        if self.buf.is_empty() {
+            self.buf.refill(&mut self.inner, DEFAULT_BUF_SIZE)?;
         }
         Ok(self.buffer())
     }
 
     fn consume(&mut self, amt: usize) {
-        self.pos = core::cmp::min(self.pos + amt, self.filled);
+        self.buf.consume(amt);
+    }
+
+    fn fill_buf_at_least(&mut self, n: usize) -> Result<&[u8]> {
+        let buf = self.peek(n)?;
+        if buf.len() < n {
+            return Err(crate::Error::UnexpectedEof);
+        }
+        Ok(buf)
     }
 }
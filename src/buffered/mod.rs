@@ -1,3 +1,16 @@
+mod buffer;
 mod bufreader;
+#[cfg(feature = "alloc")]
+mod prefetch;
+#[cfg(feature = "alloc")]
+mod readahead;
+#[cfg(feature = "alloc")]
+mod rewind;
 
 pub use self::bufreader::BufReader;
+#[cfg(feature = "alloc")]
+pub use self::prefetch::{CompleteRead, PrefetchReader, StartRead};
+#[cfg(feature = "alloc")]
+pub use self::readahead::{ReadAheadReader, ReadAheadStats};
+#[cfg(feature = "alloc")]
+pub use self::rewind::Rewind;
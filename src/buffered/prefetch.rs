@@ -0,0 +1,235 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::{
+    mem::ManuallyDrop,
+    ptr::{self, NonNull},
+};
+
+use crate::{BufRead, BufReadVectored, DEFAULT_BUF_SIZE, Error, Read, Result};
+
+/// A source that can start a read and finish it later, such as a device with
+/// asynchronous completion.
+pub trait StartRead {
+    /// Starts reading into `buf`.
+    ///
+    /// # Safety
+    ///
+    /// The memory behind `buf` must stay valid and must not be accessed by the
+    /// caller until the read has been finished with
+    /// [`CompleteRead::complete_read`].
+    unsafe fn start_read(&mut self, buf: &mut [u8]) -> Result<()>;
+}
+
+/// The completion half of [`StartRead`].
+pub trait CompleteRead: StartRead {
+    /// Waits for the read started by the last [`StartRead::start_read`] call
+    /// to finish, returning how many bytes were written to its buffer.
+    ///
+    /// A return value of 0 means the end of the source was reached.
+    fn complete_read(&mut self) -> Result<usize>;
+}
+
+/// A reader with two buffers that starts filling one of them while the caller
+/// consumes the other.
+///
+/// Whenever a buffer has been filled and handed out through [`fill_buf`], the
+/// next read is started into the other buffer, so the device can work while
+/// the data is being processed. The reader itself implements plain [`Read`]
+/// and [`BufRead`].
+///
+/// If starting the next read fails, the error is not reported until the
+/// buffer being consumed is exhausted and the read is retried.
+///
+/// [`fill_buf`]: BufRead::fill_buf
+pub struct PrefetchReader<D: CompleteRead> {
+    device: D,
+    // Owned `Box<[u8]>`s, kept as raw pointers so that moving the reader does
+    // not assert unique access to a buffer the device may be writing to.
+    bufs: [NonNull<[u8]>; 2],
+    current: usize,
+    pos: usize,
+    filled: usize,
+    in_flight: bool,
+}
+
+impl<D: CompleteRead> PrefetchReader<D> {
+    /// Creates a new `PrefetchReader<D>` with two buffers of the default size.
+    pub fn new(device: D) -> Result<PrefetchReader<D>> {
+        Self::with_capacity(DEFAULT_BUF_SIZE, device)
+    }
+
+    /// Creates a new `PrefetchReader<D>` with two buffers of `capacity` bytes
+    /// each.
+    pub fn with_capacity(capacity: usize, device: D) -> Result<PrefetchReader<D>> {
+        fn alloc_buf(capacity: usize) -> Result<Box<[u8]>> {
+            let mut buf = Vec::new();
+            buf.try_reserve_exact(capacity)
+                .map_err(|_| Error::NoMemory)?;
+            buf.resize(capacity, 0);
+            Ok(buf.into_boxed_slice())
+        }
+
+        let bufs = [alloc_buf(capacity)?, alloc_buf(capacity)?];
+        Ok(Self {
+            device,
+            // SAFETY: `Box::into_raw` never returns a null pointer.
+            bufs: bufs.map(|buf| unsafe { NonNull::new_unchecked(Box::into_raw(buf)) }),
+            current: 0,
+            pos: 0,
+            filled: 0,
+            in_flight: false,
+        })
+    }
+
+    /// Starts reading into the spare buffer if no read is in flight yet.
+    ///
+    /// This is done automatically by [`fill_buf`], but can be called earlier
+    /// to get the first read going before the data is needed.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    pub fn prefetch(&mut self) -> Result<()> {
+        if !self.in_flight {
+            // SAFETY: the spare buffer is owned by `self` and is not referenced
+            // anywhere else. It is heap-allocated, so it does not move with
+            // `self`, and it is neither read nor handed out until `wait` has
+            // completed the read. It is only freed after that, too.
+            unsafe {
+                let spare = self.bufs[self.current ^ 1].as_mut();
+                self.device.start_read(spare)?;
+            }
+            self.in_flight = true;
+        }
+        Ok(())
+    }
+
+    /// Waits for the in-flight read, if any, to finish.
+    fn wait(&mut self) -> Result<Option<usize>> {
+        if !self.in_flight {
+            return Ok(None);
+        }
+        self.in_flight = false;
+        self.device.complete_read().map(Some)
+    }
+
+    /// Gets a reference to the underlying device.
+    pub fn get_ref(&self) -> &D {
+        &self.device
+    }
+
+    /// Gets a mutable reference to the underlying device.
+    ///
+    /// It is inadvisable to start or complete reads directly on the device, as
+    /// a read may be in flight.
+    pub fn get_mut(&mut self) -> &mut D {
+        &mut self.device
+    }
+
+    /// Returns a reference to the internally buffered data.
+    ///
+    /// Unlike [`fill_buf`], this will not attempt to fill the buffer if it is
+    /// empty. Data of an in-flight read is not included.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    pub fn buffer(&self) -> &[u8] {
+        // SAFETY: the current buffer is owned by `self` and no read is ever in
+        // flight into it.
+        let current = unsafe { self.bufs[self.current].as_ref() };
+        &current[self.pos..self.filled]
+    }
+
+    /// Returns the number of bytes each of the two buffers can hold.
+    pub fn capacity(&self) -> usize {
+        self.bufs[0].len()
+    }
+
+    /// Returns `true` if a read into the spare buffer is in flight.
+    pub const fn is_prefetching(&self) -> bool {
+        self.in_flight
+    }
+
+    /// Unwraps this `PrefetchReader<D>`, returning the underlying device.
+    ///
+    /// An in-flight read is waited for and its data is lost, as is any
+    /// leftover data in the internal buffer.
+    pub fn into_inner(self) -> D {
+        let mut this = ManuallyDrop::new(self);
+        this.free_bufs();
+        // SAFETY: `this` is never used again.
+        unsafe { ptr::read(&this.device) }
+    }
+
+    /// Waits for the in-flight read, if any, and frees both buffers.
+    ///
+    /// Must be called exactly once, right before `self` goes away.
+    fn free_bufs(&mut self) {
+        // The device may still be writing to one of our buffers.
+        let _ = self.wait();
+        for buf in self.bufs {
+            // SAFETY: the buffers come from `Box::into_raw` in `with_capacity`, no
+            // read is in flight any more, and they are never used again.
+            drop(unsafe { Box::from_raw(buf.as_ptr()) });
+        }
+    }
+}
+
+// SAFETY: the buffers are owned by the reader, like the `Box<[u8]>`s they come
+// from, and are only accessed through it.
+unsafe impl<D: CompleteRead + Send> Send for PrefetchReader<D> {}
+
+// SAFETY: shared access only reads the current buffer, which no read is ever in
+// flight into.
+unsafe impl<D: CompleteRead + Sync> Sync for PrefetchReader<D> {}
+
+impl<D: CompleteRead> Read for PrefetchReader<D> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let nread = {
+            let mut rem = self.fill_buf()?;
+            rem.read(buf)?
+        };
+        self.consume(nread);
+        Ok(nread)
+    }
+
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        crate::default_buf_read_to_end_into(self, buf)
+    }
+}
+
+impl<D: CompleteRead> BufRead for PrefetchReader<D> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.pos >= self.filled {
+            self.prefetch()?;
+            let n = self.wait()?.unwrap_or(0);
+            let n = n.min(self.capacity());
+            self.current ^= 1;
+            self.pos = 0;
+            self.filled = n;
+            if n > 0 {
+                // The buffer we just left is free again: keep the device busy
+                // while the caller works on the new one.
+                let _ = self.prefetch();
+            }
+        }
+        Ok(self.buffer())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        #[cfg(feature = "no-panic")]
+        let amt = core::cmp::min(amt, self.filled - self.pos);
+        self.pos = core::cmp::min(self.pos + amt, self.filled);
+    }
+}
+
+impl<D: CompleteRead> BufReadVectored for PrefetchReader<D> {
+    /// Returns the current buffer only: the other one is not handed out
+    /// before its read is completed, which only happens once the current
+    /// buffer is exhausted.
+    fn fill_bufs(&mut self) -> Result<[&[u8]; 2]> {
+        Ok([self.fill_buf()?, &[]])
+    }
+}
+
+impl<D: CompleteRead> Drop for PrefetchReader<D> {
+    fn drop(&mut self) {
+        self.free_bufs();
+    }
+}
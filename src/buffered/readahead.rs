@@ -0,0 +1,292 @@
+use alloc::{string::String, vec::Vec};
+use core::{io::BorrowedCursor, mem::MaybeUninit};
+
+use super::buffer::Buffer;
+use crate::{BufRead, DEFAULT_BUF_SIZE, Error, Read, Result, Seek, SeekFrom};
+
+/// Default upper limit of the read-ahead window.
+const DEFAULT_MAX_WINDOW: usize = DEFAULT_BUF_SIZE * 64;
+
+/// Counters collected by a [`ReadAheadReader`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ReadAheadStats {
+    /// Number of times the internal buffer was refilled from the inner reader.
+    pub refills: u64,
+    /// Number of reads that were large enough to bypass the internal buffer.
+    pub bypassed_reads: u64,
+    /// Number of refills that found the stream being read sequentially and
+    /// grew the read-ahead window.
+    pub window_grows: u64,
+    /// Number of times the window was shrunk back because of a seek.
+    pub window_resets: u64,
+}
+
+/// A heap-backed buffered reader that adapts its read-ahead window to the
+/// access pattern.
+///
+/// Every time the buffer is drained and the previous refill returned a full
+/// window, the stream is considered sequential and the next refill asks the
+/// inner reader for twice as much data, up to a configured maximum. Seeking
+/// outside the buffered data shrinks the window back to its minimum.
+///
+/// If the buffer cannot be grown because memory is exhausted, the reader keeps
+/// using its current window.
+pub struct ReadAheadReader<R> {
+    inner: R,
+    buf: Buffer<Vec<MaybeUninit<u8>>>,
+    window: usize,
+    min_window: usize,
+    max_window: usize,
+    sequential: bool,
+    stats: ReadAheadStats,
+}
+
+impl<R: Read> ReadAheadReader<R> {
+    /// Creates a new `ReadAheadReader<R>` whose window starts at the default
+    /// buffer size and may grow up to 64 times that.
+    pub fn new(inner: R) -> ReadAheadReader<R> {
+        Self::with_window(inner, DEFAULT_BUF_SIZE, DEFAULT_MAX_WINDOW)
+    }
+
+    /// Creates a new `ReadAheadReader<R>` whose window starts at `min` bytes and
+    /// may grow up to `max` bytes.
+    ///
+    /// `min` is raised to 1 and `max` to `min` if they are smaller.
+    pub fn with_window(inner: R, min: usize, max: usize) -> ReadAheadReader<R> {
+        let min = min.max(1);
+        Self {
+            inner,
+            buf: Buffer::new(Vec::new()),
+            window: min,
+            min_window: min,
+            max_window: max.max(min),
+            sequential: false,
+            stats: ReadAheadStats::default(),
+        }
+    }
+}
+
+impl<R> ReadAheadReader<R> {
+    /// Gets a reference to the underlying reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Returns a reference to the internally buffered data.
+    ///
+    /// Unlike [`fill_buf`], this will not attempt to fill the buffer if it is empty.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    pub fn buffer(&self) -> &[u8] {
+        self.buf.buffer()
+    }
+
+    /// Returns the number of bytes the next refill will ask the inner reader
+    /// for.
+    pub const fn current_window(&self) -> usize {
+        self.window
+    }
+
+    /// Returns the lower and upper limits of the read-ahead window.
+    pub const fn window_limits(&self) -> (usize, usize) {
+        (self.min_window, self.max_window)
+    }
+
+    /// Returns the counters collected so far.
+    pub const fn stats(&self) -> ReadAheadStats {
+        self.stats
+    }
+
+    /// Resets all counters to zero.
+    pub fn reset_stats(&mut self) {
+        self.stats = ReadAheadStats::default();
+    }
+
+    /// Unwraps this `ReadAheadReader<R>`, returning the underlying reader.
+    ///
+    /// Note that any leftover data in the internal buffer is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn reset_window(&mut self) {
+        if self.sequential || self.window != self.min_window {
+            self.stats.window_resets += 1;
+        }
+        self.window = self.min_window;
+        self.sequential = false;
+    }
+
+    /// Makes sure the buffer can hold `self.window` bytes, falling back to the
+    /// current allocation if it cannot be grown.
+    fn reserve_window(&mut self) -> Result<()> {
+        let len = self.buf.capacity();
+        if self.buf.try_grow(self.window).is_err() {
+            if len == 0 {
+                return Err(Error::NoMemory);
+            }
+            self.window = len;
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> ReadAheadReader<R> {
+    fn refill(&mut self) -> Result<()> {
+        if self.sequential {
+            let grown = self.window.saturating_mul(2).min(self.max_window);
+            if grown > self.window {
+                self.window = grown;
+                self.stats.window_grows += 1;
+            }
+        }
+        self.reserve_window()?;
+
+        let result = self.buf.refill(&mut self.inner, self.window);
+        self.stats.refills += 1;
+        // A full window means the reader is keeping up with a sequential
+        // stream, so the next refill may ask for more.
+        self.sequential = self.buf.len() == self.window;
+
+        result
+    }
+}
+
+impl<R: Read> Read for ReadAheadReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        // If we don't have any buffered data and we're doing a read at least as
+        // large as our window, bypass our internal buffer entirely.
+        if self.buf.is_empty() && buf.len() >= self.window {
+            self.buf.discard_buffer();
+            self.stats.bypassed_reads += 1;
+            return self.inner.read(buf);
+        }
+        let nread = {
+            let mut rem = self.fill_buf()?;
+            rem.read(buf)?
+        };
+        self.consume(nread);
+        Ok(nread)
+    }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        if self.buf.is_empty() && cursor.capacity() >= self.window {
+            self.buf.discard_buffer();
+            self.stats.bypassed_reads += 1;
+            return self.inner.read_buf(cursor);
+        }
+
+        let prev = cursor.written();
+
+        let mut rem = self.fill_buf()?;
+        rem.read_buf(cursor.reborrow())?;
+
+        self.consume(cursor.written() - prev);
+        Ok(())
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        if self
+            .buf
+            .consume_with(buf.len(), |claimed| buf.copy_from_slice(claimed))
+        {
+            return Ok(());
+        }
+        crate::default_read_exact(self, buf)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.buf.size_hint(self.inner.size_hint())
+    }
+
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        crate::default_buf_read_to_end_into(self, buf)
+    }
+
+    // The inner reader might have an optimized `read_to_end`. Drain our buffer and then
+    // delegate to the inner implementation.
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        self.buf.read_to_end(&mut self.inner, buf)
+    }
+
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        self.buf.read_to_string(&mut self.inner, buf)
+    }
+}
+
+impl<R: Read> BufRead for ReadAheadReader<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.buf.is_empty() {
+            self.refill()?;
+        }
+        Ok(self.buffer())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.buf.consume(amt);
+    }
+}
+
+impl<R: Seek> Seek for ReadAheadReader<R> {
+    /// Seek to an offset, in bytes, in the underlying reader.
+    ///
+    /// The position used for seeking with [`SeekFrom::Current`] is the position
+    /// the underlying reader would be at if the `ReadAheadReader<R>` had no
+    /// internal buffer.
+    ///
+    /// Seeking always discards the internal buffer and shrinks the read-ahead
+    /// window back to its minimum. Use [`seek_relative`] to move within the
+    /// buffered data without losing the sequential state.
+    ///
+    /// [`seek_relative`]: Seek::seek_relative
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let result;
+        if let SeekFrom::Current(n) = pos {
+            let remainder = self.buf.len() as i64;
+            // It should be safe to assume that remainder fits within an i64 as the alternative
+            // means we managed to allocate 8 exbibytes and that's absurd.
+            // But it's not out of the realm of possibility for some weird underlying reader to
+            // support seeking by i64::MIN so we need to handle underflow when subtracting
+            // remainder.
+            if let Some(offset) = n.checked_sub(remainder) {
+                result = self.inner.seek(SeekFrom::Current(offset))?;
+            } else {
+                // seek backwards by our remainder, and then by the offset
+                self.inner.seek(SeekFrom::Current(-remainder))?;
+                self.buf.discard_buffer();
+                result = self.inner.seek(SeekFrom::Current(n))?;
+            }
+        } else {
+            // Seeking with Start/End doesn't care about our buffer length.
+            result = self.inner.seek(pos)?;
+        }
+        self.buf.discard_buffer();
+        self.reset_window();
+        Ok(result)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        let remainder = self.buf.len() as u64;
+        self.inner
+            .stream_position()?
+            .checked_sub(remainder)
+            .ok_or(Error::InvalidData)
+    }
+
+    /// Seeks relative to the current position.
+    ///
+    /// If the new position lies within the buffer, the buffer will not be
+    /// flushed and the read-ahead window is kept. This allows for more
+    /// efficient seeking.
+    fn seek_relative(&mut self, offset: i64) -> Result<()> {
+        if self.buf.seek_relative(offset) {
+            return Ok(());
+        }
+        self.seek(SeekFrom::Current(offset)).map(drop)
+    }
+}
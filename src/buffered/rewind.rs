@@ -0,0 +1,178 @@
+use alloc::vec::Vec;
+use core::{cmp, io::BorrowedBuf};
+
+use crate::{BufRead, DEFAULT_BUF_SIZE, Error, Read, Result};
+
+/// A reader that can go back to a marked position by replaying the bytes read
+/// since then, for sources that cannot seek.
+///
+/// After [`mark`], up to `limit` bytes read are recorded. [`reset`] rewinds
+/// to the mark, and the recorded bytes are read again before any new data.
+/// This is enough to sniff a magic number and then hand the whole stream to
+/// the right parser.
+///
+/// If more than `limit` bytes are read after the mark, the mark is dropped and
+/// [`reset`] fails with [`Error::OutOfRange`].
+///
+/// [`mark`]: Rewind::mark
+/// [`reset`]: Rewind::reset
+#[derive(Debug)]
+pub struct Rewind<R> {
+    inner: R,
+    buf: Vec<u8>,
+    pos: usize,
+    limit: usize,
+    marked: bool,
+    overflowed: bool,
+}
+
+impl<R: Read> Rewind<R> {
+    /// Creates a new `Rewind<R>` that can replay up to `limit` bytes.
+    pub const fn new(inner: R, limit: usize) -> Rewind<R> {
+        Rewind {
+            inner,
+            buf: Vec::new(),
+            pos: 0,
+            limit,
+            marked: false,
+            overflowed: false,
+        }
+    }
+
+    /// Starts recording at the current position.
+    ///
+    /// Any previous mark is replaced.
+    pub fn mark(&mut self) {
+        // Bytes that were replayed are before the new mark.
+        self.buf.drain(..self.pos);
+        self.pos = 0;
+        self.marked = true;
+        self.overflowed = false;
+    }
+
+    /// Stops recording, and drops the mark.
+    pub fn clear_mark(&mut self) {
+        self.marked = false;
+        self.overflowed = false;
+    }
+
+    /// Rewinds to the mark.
+    ///
+    /// Returns [`Error::OutOfRange`] if more than `limit` bytes were read since
+    /// the mark, and [`Error::BadState`] if there is no mark. The mark stays in
+    /// place, so the reader can be reset again.
+    pub fn reset(&mut self) -> Result<()> {
+        if self.overflowed {
+            return Err(Error::OutOfRange);
+        }
+        if !self.marked {
+            return Err(Error::BadState);
+        }
+        self.pos = 0;
+        Ok(())
+    }
+
+    /// Returns `true` if a mark is set and can still be reset to.
+    pub const fn is_marked(&self) -> bool {
+        self.marked
+    }
+
+    /// Returns the maximum number of bytes that can be replayed.
+    pub const fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader are not recorded.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Rewind<R>`, returning the underlying reader.
+    ///
+    /// Note that any recorded data that has not been replayed is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for Rewind<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        // Nothing to replay or record: bypass the buffer.
+        if !self.marked && self.pos >= self.buf.len() {
+            return self.inner.read(buf);
+        }
+        let nread = {
+            let mut rem = self.fill_buf()?;
+            rem.read(buf)?
+        };
+        self.consume(nread);
+        Ok(nread)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buf.len() - self.pos;
+        let (lower, upper) = self.inner.size_hint();
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|u| u.checked_add(buffered)),
+        )
+    }
+
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        crate::default_buf_read_to_end_into(self, buf)
+    }
+}
+
+impl<R: Read> BufRead for Rewind<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.pos >= self.buf.len() {
+            if !self.marked {
+                self.buf.clear();
+                self.pos = 0;
+            }
+            let len = self.buf.len();
+            // Stay within the replay window while it has room. Once it is
+            // full, read ahead anyway: the mark is only lost if those bytes
+            // are consumed.
+            let want = if self.marked && len < self.limit {
+                cmp::min(self.limit - len, DEFAULT_BUF_SIZE)
+            } else {
+                DEFAULT_BUF_SIZE
+            };
+            self.buf.try_reserve(want).map_err(|_| Error::NoMemory)?;
+            let spare = self.buf.spare_capacity_mut();
+            #[cfg(feature = "no-panic")]
+            let want = cmp::min(want, spare.len());
+            let mut buf = BorrowedBuf::from(&mut spare[..want]);
+            let res = self.inner.read_buf(buf.unfilled());
+            let n = buf.len();
+            // SAFETY: `read_buf` filled the first `n` bytes of the spare
+            // capacity.
+            unsafe { self.buf.set_len(len + n) };
+            res?;
+        }
+        #[cfg(feature = "no-panic")]
+        {
+            self.pos = cmp::min(self.pos, self.buf.len());
+        }
+        Ok(&self.buf[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        #[cfg(feature = "no-panic")]
+        let amt = cmp::min(amt, self.buf.len() - self.pos);
+        self.pos = cmp::min(self.pos + amt, self.buf.len());
+        // Data buffered before the mark may go past the limit without a refill.
+        if self.marked && self.pos > self.limit {
+            self.marked = false;
+            self.overflowed = true;
+        }
+    }
+}
@@ -0,0 +1,284 @@
+use alloc::vec::Vec;
+use core::{cmp, iter};
+
+use crate::{BufRead, BufReadVectored, Error, Read, Result, Seek, SeekFrom, Write};
+
+/// A byte stream stored as a list of owned chunks.
+///
+/// Chunks are appended with [`push_chunk`] or through [`Write`], which stores
+/// each written buffer as a new chunk. Reading goes through the chunks in
+/// order without concatenating them: [`fill_buf`] returns the rest of the
+/// current chunk.
+///
+/// Like [`Cursor`], a `ChunkList` keeps a read position that can be moved
+/// with [`Seek`]. Writing always appends to the end, regardless of that
+/// position.
+///
+/// [`push_chunk`]: ChunkList::push_chunk
+/// [`fill_buf`]: BufRead::fill_buf
+/// [`Cursor`]: crate::Cursor
+#[derive(Debug, Default, Clone)]
+pub struct ChunkList {
+    chunks: Vec<Vec<u8>>,
+    len: usize,
+    pos: u64,
+    // Chunk and offset within it that `pos` points to. `idx == chunks.len()`
+    // when `pos` is at or past the end.
+    idx: usize,
+    off: usize,
+}
+
+impl ChunkList {
+    /// Creates a new, empty `ChunkList`.
+    pub const fn new() -> ChunkList {
+        ChunkList {
+            chunks: Vec::new(),
+            len: 0,
+            pos: 0,
+            idx: 0,
+            off: 0,
+        }
+    }
+
+    /// Appends a chunk to the end of the list without copying it.
+    ///
+    /// Empty chunks are ignored.
+    pub fn push_chunk(&mut self, chunk: impl Into<Vec<u8>>) -> Result<()> {
+        let chunk = chunk.into();
+        if chunk.is_empty() {
+            return Ok(());
+        }
+        self.chunks.try_reserve(1).map_err(|_| Error::NoMemory)?;
+        let start = self.len as u64;
+        self.len += chunk.len();
+        // Only a position at or past the old end can fall into the new chunk.
+        if self.idx == self.chunks.len() && self.pos < self.len as u64 {
+            self.off = (self.pos - start) as usize;
+        } else if self.idx == self.chunks.len() {
+            self.idx += 1;
+        }
+        self.chunks.push(chunk);
+        Ok(())
+    }
+
+    /// Returns the total number of bytes in all chunks.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the list holds no data.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of chunks.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// Returns an iterator over the chunks, from first to last.
+    pub fn chunks(&self) -> impl ExactSizeIterator<Item = &[u8]> {
+        self.chunks.iter().map(|c| &c[..])
+    }
+
+    /// Returns the current read position.
+    pub const fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Returns the number of bytes between the read position and the end.
+    pub fn remaining(&self) -> usize {
+        (self.len as u64).saturating_sub(self.pos) as usize
+    }
+
+    /// Moves the read position forward by `n` bytes, crossing chunk boundaries
+    /// as needed.
+    ///
+    /// The position does not move past the end of the data.
+    pub fn advance(&mut self, n: usize) {
+        let mut n = cmp::min(n, self.remaining());
+        self.pos += n as u64;
+        while n > 0 {
+            #[cfg(feature = "no-panic")]
+            if self.idx >= self.chunks.len() {
+                break;
+            }
+            let avail = self.chunks[self.idx].len() - self.off;
+            if n < avail {
+                self.off += n;
+                break;
+            }
+            n -= avail;
+            self.idx += 1;
+            self.off = 0;
+        }
+    }
+
+    /// Splits the list into two at the given byte offset.
+    ///
+    /// Returns a newly allocated list containing the bytes `[at, len)`, and
+    /// leaves `self` with the bytes `[0, at)`. Only the chunk containing `at`,
+    /// if any, is copied. The read position is split as well: a position past
+    /// `at` moves into the returned list.
+    ///
+    /// Returns [`Error::InvalidInput`] if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> Result<ChunkList> {
+        if at > self.len {
+            return Err(Error::InvalidInput);
+        }
+        let (idx, off) = self.locate(at as u64);
+
+        let mut tail = Vec::new();
+        tail.try_reserve_exact(self.chunks.len() - idx)
+            .map_err(|_| Error::NoMemory)?;
+        #[cfg(feature = "no-panic")]
+        if tail.capacity() < self.chunks.len() - idx {
+            return Err(Error::NoMemory);
+        }
+        if let Some(chunk) = self.chunks.get_mut(idx).filter(|_| off > 0) {
+            let split = chunk.get(off..).unwrap_or_default();
+            let mut rest = Vec::new();
+            rest.try_reserve_exact(split.len())
+                .map_err(|_| Error::NoMemory)?;
+            rest.extend_from_slice(split);
+            chunk.truncate(off);
+            tail.extend(iter::once(rest).chain(self.chunks.drain(idx + 1..)));
+        } else {
+            tail.extend(self.chunks.drain(idx..));
+        }
+
+        let mut other = ChunkList {
+            chunks: tail,
+            len: self.len - at,
+            pos: self.pos.saturating_sub(at as u64),
+            idx: 0,
+            off: 0,
+        };
+        other.relocate();
+        self.len = at;
+        self.pos = cmp::min(self.pos, at as u64);
+        self.relocate();
+        Ok(other)
+    }
+
+    /// Consumes the list, returning its chunks.
+    pub fn into_chunks(self) -> Vec<Vec<u8>> {
+        self.chunks
+    }
+
+    /// Finds the chunk and offset within it that `pos` points to.
+    fn locate(&self, pos: u64) -> (usize, usize) {
+        let mut rem = pos;
+        for (i, chunk) in self.chunks.iter().enumerate() {
+            if rem < chunk.len() as u64 {
+                return (i, rem as usize);
+            }
+            rem -= chunk.len() as u64;
+        }
+        (self.chunks.len(), 0)
+    }
+
+    fn relocate(&mut self) {
+        (self.idx, self.off) = self.locate(self.pos);
+    }
+}
+
+impl From<Vec<u8>> for ChunkList {
+    fn from(chunk: Vec<u8>) -> Self {
+        let len = chunk.len();
+        let chunks = if chunk.is_empty() {
+            Vec::new()
+        } else {
+            alloc::vec![chunk]
+        };
+        ChunkList {
+            chunks,
+            len,
+            pos: 0,
+            idx: 0,
+            off: 0,
+        }
+    }
+}
+
+impl Read for ChunkList {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let mut nread = 0;
+        while nread < buf.len() {
+            let src = self.fill_buf()?;
+            if src.is_empty() {
+                break;
+            }
+            let n = cmp::min(src.len(), buf.len() - nread);
+            buf[nread..nread + n].copy_from_slice(&src[..n]);
+            self.consume(n);
+            nread += n;
+        }
+        Ok(nread)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining();
+        (len, Some(len))
+    }
+
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        crate::default_buf_read_to_end_into(self, buf)
+    }
+}
+
+impl BufRead for ChunkList {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(match self.chunks.get(self.idx) {
+            Some(chunk) => &chunk[self.off..],
+            None => &[],
+        })
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.advance(amt);
+    }
+}
+
+impl BufReadVectored for ChunkList {
+    /// Returns the rest of the current chunk and the chunk after it.
+    fn fill_bufs(&mut self) -> Result<[&[u8]; 2]> {
+        let mut chunks = self.chunks.get(self.idx..).unwrap_or_default().iter();
+        let current = chunks.next().map_or(&[][..], |chunk| &chunk[self.off..]);
+        let next = chunks.next().map_or(&[][..], |chunk| &chunk[..]);
+        Ok([current, next])
+    }
+}
+
+impl Seek for ChunkList {
+    fn seek(&mut self, style: SeekFrom) -> Result<u64> {
+        self.pos = crate::seek::resolve_seek(style, self.pos, self.len as u64)?;
+        self.relocate();
+        Ok(self.pos)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        Ok(self.len as u64)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+impl Write for ChunkList {
+    /// Appends a copy of `buf` as a new chunk.
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let mut chunk = Vec::new();
+        chunk
+            .try_reserve_exact(buf.len())
+            .map_err(|_| Error::NoMemory)?;
+        chunk.extend_from_slice(buf);
+        self.push_chunk(chunk)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
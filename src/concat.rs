@@ -0,0 +1,210 @@
+use alloc::vec::Vec;
+use core::cmp;
+
+use crate::{Error, Read, Result, Seek, SeekFrom, Write};
+
+/// A seekable stream made of several seekable parts of known lengths, such as
+/// the pieces of a split disk image.
+///
+/// Offsets of the whole stream are mapped to a part and an offset within it.
+/// Reads and writes go across part boundaries, and seeking follows the rules
+/// documented on [`SeekFrom`], with [`SeekFrom::End`] relative to the sum of
+/// the part lengths.
+///
+/// Writes never grow the stream: writing at or past the end writes nothing.
+/// A part is only seeked when it is not already at the offset it is needed
+/// at.
+#[derive(Debug)]
+pub struct ConcatSeek<R> {
+    parts: Vec<R>,
+    // End offset of each part in the whole stream.
+    ends: Vec<u64>,
+    pos: u64,
+    // Part whose own position is known, and that position.
+    synced: Option<(usize, u64)>,
+}
+
+impl<R> Default for ConcatSeek<R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<R> ConcatSeek<R> {
+    /// Creates a new, empty `ConcatSeek<R>`.
+    pub const fn new() -> ConcatSeek<R> {
+        ConcatSeek {
+            parts: Vec::new(),
+            ends: Vec::new(),
+            pos: 0,
+            synced: None,
+        }
+    }
+
+    /// Appends a part of `len` bytes to the end of the stream.
+    pub fn push(&mut self, part: R, len: u64) -> Result<()> {
+        let end = self.len().checked_add(len).ok_or(Error::InvalidInput)?;
+        self.parts.try_reserve(1).map_err(|_| Error::NoMemory)?;
+        self.ends.try_reserve(1).map_err(|_| Error::NoMemory)?;
+        self.parts.push(part);
+        self.ends.push(end);
+        Ok(())
+    }
+
+    /// Returns the total length of all parts.
+    pub fn len(&self) -> u64 {
+        self.ends.last().copied().unwrap_or(0)
+    }
+
+    /// Returns `true` if the stream holds no data.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of parts.
+    pub fn part_count(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// Gets a reference to the part at `index`.
+    pub fn get_ref(&self, index: usize) -> Option<&R> {
+        self.parts.get(index)
+    }
+
+    /// Gets a mutable reference to the part at `index`.
+    ///
+    /// The part may be seeked freely, it is seeked back before it is used
+    /// again.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut R> {
+        self.synced = None;
+        self.parts.get_mut(index)
+    }
+
+    /// Consumes the stream, returning its parts.
+    pub fn into_parts(self) -> Vec<R> {
+        self.parts
+    }
+
+    /// Finds the part that `pos` is in and the offset within it, and how many
+    /// bytes of the part are left from there.
+    fn locate(&self, pos: u64) -> Option<(usize, u64, u64)> {
+        let idx = self.ends.partition_point(|&end| end <= pos);
+        let end = *self.ends.get(idx)?;
+        let start = if idx == 0 { 0 } else { self.ends[idx - 1] };
+        Some((idx, pos - start, end - pos))
+    }
+}
+
+impl<R: Seek> ConcatSeek<R> {
+    /// Returns the part at the current position, seeked to where it is
+    /// needed, and how many bytes of it are left. Returns `None` at the end.
+    fn current(&mut self) -> Result<Option<(usize, u64)>> {
+        let Some((idx, local, left)) = self.locate(self.pos) else {
+            return Ok(None);
+        };
+        if self.synced != Some((idx, local)) {
+            self.synced = None;
+            self.parts[idx].seek(SeekFrom::Start(local))?;
+            self.synced = Some((idx, local));
+        }
+        Ok(Some((idx, left)))
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.pos += n as u64;
+        self.synced = self.synced.map(|(idx, local)| (idx, local + n as u64));
+    }
+}
+
+impl<R: Read + Seek> Read for ConcatSeek<R> {
+    /// Reads from as many parts as needed to fill `buf`.
+    ///
+    /// If a part ends before its stated length, the read stops there; the
+    /// next one then fails with [`Error::UnexpectedEof`].
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let mut nread = 0;
+        while nread < buf.len() {
+            let (idx, left) = match self.current() {
+                Ok(Some(part)) => part,
+                Ok(None) => break,
+                Err(e) if nread == 0 => return Err(e),
+                Err(_) => break,
+            };
+            let max = cmp::min((buf.len() - nread) as u64, left) as usize;
+            match self.parts[idx].read(&mut buf[nread..nread + max]) {
+                Ok(0) if nread == 0 => return Err(Error::UnexpectedEof),
+                Ok(0) => break,
+                Ok(n) => {
+                    let n = cmp::min(n, max);
+                    self.advance(n);
+                    nread += n;
+                }
+                Err(e) => {
+                    self.synced = None;
+                    if nread == 0 {
+                        return Err(e);
+                    }
+                    break;
+                }
+            }
+        }
+        Ok(nread)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = usize::try_from(self.len().saturating_sub(self.pos)).ok();
+        (0, left)
+    }
+}
+
+impl<R: Write + Seek> Write for ConcatSeek<R> {
+    /// Writes to as many parts as needed to write all of `buf`, as long as it
+    /// fits before the end of the stream.
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let mut written = 0;
+        while written < buf.len() {
+            let (idx, left) = match self.current() {
+                Ok(Some(part)) => part,
+                Ok(None) => break,
+                Err(e) if written == 0 => return Err(e),
+                Err(_) => break,
+            };
+            let max = cmp::min((buf.len() - written) as u64, left) as usize;
+            match self.parts[idx].write(&buf[written..written + max]) {
+                Ok(0) => break,
+                Ok(n) => {
+                    let n = cmp::min(n, max);
+                    self.advance(n);
+                    written += n;
+                }
+                Err(e) => {
+                    self.synced = None;
+                    if written == 0 {
+                        return Err(e);
+                    }
+                    break;
+                }
+            }
+        }
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.parts.iter_mut().try_for_each(Write::flush)
+    }
+}
+
+impl<R> Seek for ConcatSeek<R> {
+    fn seek(&mut self, style: SeekFrom) -> Result<u64> {
+        self.pos = crate::seek::resolve_seek(style, self.pos, self.len())?;
+        Ok(self.pos)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        Ok(self.len())
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
@@ -0,0 +1,417 @@
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, vec::Vec};
+#[cfg(feature = "alloc")]
+use core::fmt;
+use core::io::BorrowedCursor;
+
+use crate::{BufRead, Error, Read, Result, Seek, SeekFrom, Write};
+
+/// A reader adapter that tracks the byte offset of the underlying stream and
+/// labels it with the operation being performed.
+///
+/// When an error occurs, the offset at which it happened is remembered and can
+/// be retrieved with [`error_offset`].
+///
+/// [`error_offset`]: ContextReader::error_offset
+#[derive(Debug)]
+pub struct ContextReader<R> {
+    inner: R,
+    offset: u64,
+    operation: &'static str,
+    error_offset: Option<u64>,
+    // Length of the buffer returned by the last `fill_buf`, which bounds what
+    // `consume` can advance by.
+    buffered: usize,
+}
+
+impl<R> ContextReader<R> {
+    /// Creates a new `ContextReader<R>` starting at offset 0.
+    pub const fn new(inner: R, operation: &'static str) -> ContextReader<R> {
+        Self::with_offset(inner, operation, 0)
+    }
+
+    /// Creates a new `ContextReader<R>` whose underlying stream is already at
+    /// `offset`.
+    pub const fn with_offset(inner: R, operation: &'static str, offset: u64) -> ContextReader<R> {
+        Self {
+            inner,
+            offset,
+            operation,
+            error_offset: None,
+            buffered: 0,
+        }
+    }
+
+    /// Returns the number of bytes consumed from the underlying stream so far,
+    /// plus the initial offset.
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the label of the current operation.
+    pub const fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// Changes the label of the current operation.
+    pub fn set_operation(&mut self, operation: &'static str) {
+        self.operation = operation;
+    }
+
+    /// Returns the offset at which the most recent error occurred, if any.
+    pub const fn error_offset(&self) -> Option<u64> {
+        self.error_offset
+    }
+
+    /// Wraps `error` with the current offset and operation label.
+    #[cfg(feature = "alloc")]
+    pub fn context_error(&self, error: Error) -> ContextError {
+        ContextError::new(error)
+            .with_offset(self.error_offset.unwrap_or(self.offset))
+            .context(self.operation)
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader are not accounted for.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `ContextReader<R>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn track<T>(&mut self, res: Result<T>) -> Result<T> {
+        if res.is_err() {
+            self.error_offset = Some(self.offset);
+        }
+        res
+    }
+}
+
+impl<R: Read> Read for ContextReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.buffered = 0;
+        let res = self.inner.read(buf);
+        if let Ok(n) = res {
+            self.offset += n as u64;
+        }
+        self.track(res)
+    }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        self.buffered = 0;
+        let prev_written = cursor.written();
+        let res = self.inner.read_buf(cursor.reborrow());
+        self.offset += (cursor.written() - prev_written) as u64;
+        self.track(res)
+    }
+
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.buffered = 0;
+        let res = self.inner.read_to_end_into(buf);
+        match res {
+            Ok(n) => self.offset += n as u64,
+            // `buf` was filled before the end was reached.
+            Err(Error::OutOfRange) => self.offset += buf.len() as u64,
+            Err(_) => {}
+        }
+        self.track(res)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<R: BufRead> BufRead for ContextReader<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        match self.inner.fill_buf() {
+            Ok(buf) => {
+                self.buffered = buf.len();
+                Ok(buf)
+            }
+            Err(e) => {
+                self.error_offset = Some(self.offset);
+                Err(e)
+            }
+        }
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = amt.min(self.buffered);
+        self.buffered -= amt;
+        self.offset += amt as u64;
+        self.inner.consume(amt);
+    }
+
+    fn fill_buf_at_least(&mut self, n: usize) -> Result<&[u8]> {
+        match self.inner.fill_buf_at_least(n) {
+            Ok(buf) => {
+                self.buffered = buf.len();
+                Ok(buf)
+            }
+            Err(e) => {
+                self.error_offset = Some(self.offset);
+                Err(e)
+            }
+        }
+    }
+}
+
+impl<R: Seek> Seek for ContextReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.buffered = 0;
+        let res = self.inner.seek(pos);
+        if let Ok(pos) = res {
+            self.offset = pos;
+        }
+        self.track(res)
+    }
+}
+
+/// A writer adapter that tracks the byte offset of the underlying stream and
+/// labels it with the operation being performed.
+///
+/// When an error occurs, the offset at which it happened is remembered and can
+/// be retrieved with [`error_offset`].
+///
+/// [`error_offset`]: ContextWriter::error_offset
+#[derive(Debug)]
+pub struct ContextWriter<W> {
+    inner: W,
+    offset: u64,
+    operation: &'static str,
+    error_offset: Option<u64>,
+}
+
+impl<W> ContextWriter<W> {
+    /// Creates a new `ContextWriter<W>` starting at offset 0.
+    pub const fn new(inner: W, operation: &'static str) -> ContextWriter<W> {
+        Self::with_offset(inner, operation, 0)
+    }
+
+    /// Creates a new `ContextWriter<W>` whose underlying stream is already at
+    /// `offset`.
+    pub const fn with_offset(inner: W, operation: &'static str, offset: u64) -> ContextWriter<W> {
+        Self {
+            inner,
+            offset,
+            operation,
+            error_offset: None,
+        }
+    }
+
+    /// Returns the number of bytes written to the underlying stream so far,
+    /// plus the initial offset.
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the label of the current operation.
+    pub const fn operation(&self) -> &'static str {
+        self.operation
+    }
+
+    /// Changes the label of the current operation.
+    pub fn set_operation(&mut self, operation: &'static str) {
+        self.operation = operation;
+    }
+
+    /// Returns the offset at which the most recent error occurred, if any.
+    pub const fn error_offset(&self) -> Option<u64> {
+        self.error_offset
+    }
+
+    /// Wraps `error` with the current offset and operation label.
+    #[cfg(feature = "alloc")]
+    pub fn context_error(&self, error: Error) -> ContextError {
+        ContextError::new(error)
+            .with_offset(self.error_offset.unwrap_or(self.offset))
+            .context(self.operation)
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer are not accounted for.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `ContextWriter<W>`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    fn track<T>(&mut self, res: Result<T>) -> Result<T> {
+        if res.is_err() {
+            self.error_offset = Some(self.offset);
+        }
+        res
+    }
+}
+
+impl<W: Write> Write for ContextWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let res = self.inner.write(buf);
+        if let Ok(n) = res {
+            self.offset += n as u64;
+        }
+        self.track(res)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        let res = self.inner.flush();
+        self.track(res)
+    }
+}
+
+impl<W: Seek> Seek for ContextWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let res = self.inner.seek(pos);
+        if let Ok(pos) = res {
+            self.offset = pos;
+        }
+        self.track(res)
+    }
+}
+
+/// An [`Error`] enriched with the stream offset and a chain of messages
+/// describing what was being done when it occurred.
+///
+/// It converts back into a plain [`Error`] with the original error code, so it
+/// can be propagated with `?` from functions returning [`Result`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct ContextError {
+    error: Error,
+    offset: Option<u64>,
+    messages: Vec<Cow<'static, str>>,
+}
+
+#[cfg(feature = "alloc")]
+impl ContextError {
+    /// Creates a new `ContextError` without any context attached.
+    pub const fn new(error: Error) -> ContextError {
+        Self {
+            error,
+            offset: None,
+            messages: Vec::new(),
+        }
+    }
+
+    /// Returns the underlying error.
+    pub const fn error(&self) -> Error {
+        self.error
+    }
+
+    /// Returns the stream offset at which the error occurred, if known.
+    pub const fn offset(&self) -> Option<u64> {
+        self.offset
+    }
+
+    /// Returns the attached messages, from the innermost to the outermost.
+    pub fn messages(&self) -> impl DoubleEndedIterator<Item = &str> {
+        self.messages.iter().map(|m| &**m)
+    }
+
+    /// Sets the stream offset at which the error occurred.
+    ///
+    /// An offset that is already present is kept, since it was recorded closer
+    /// to where the error happened.
+    pub fn with_offset(mut self, offset: u64) -> ContextError {
+        self.offset.get_or_insert(offset);
+        self
+    }
+
+    /// Attaches a message describing what was being done.
+    ///
+    /// The message is silently dropped if there is no memory left to store it.
+    pub fn context(mut self, msg: impl Into<Cow<'static, str>>) -> ContextError {
+        if self.messages.try_reserve(1).is_ok() {
+            self.messages.push(msg.into());
+        }
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Error> for ContextError {
+    fn from(error: Error) -> Self {
+        ContextError::new(error)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<ContextError> for Error {
+    fn from(e: ContextError) -> Self {
+        e.error
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for msg in self.messages.iter().rev() {
+            write!(f, "{msg}: ")?;
+        }
+        if let Some(offset) = self.offset {
+            write!(f, "at offset {offset:#x}: ")?;
+        }
+        write!(f, "{}", self.error)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for ContextError {}
+
+/// Extension methods for attaching context to the error of a [`Result`].
+///
+/// Messages are stored on the heap. Attaching context never fails: if there
+/// is no memory left to store a message, the message is dropped, and the error
+/// code and offset are kept as they are.
+#[cfg(feature = "alloc")]
+pub trait ResultExt<T> {
+    /// Records the stream offset at which the error occurred.
+    fn with_offset(self, offset: u64) -> core::result::Result<T, ContextError>;
+
+    /// Attaches a message describing what was being done.
+    fn context(self, msg: impl Into<Cow<'static, str>>) -> core::result::Result<T, ContextError>;
+
+    /// Attaches a lazily evaluated message describing what was being done.
+    fn with_context<F, M>(self, f: F) -> core::result::Result<T, ContextError>
+    where
+        F: FnOnce() -> M,
+        M: Into<Cow<'static, str>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T, E: Into<ContextError>> ResultExt<T> for core::result::Result<T, E> {
+    fn with_offset(self, offset: u64) -> core::result::Result<T, ContextError> {
+        self.map_err(|e| e.into().with_offset(offset))
+    }
+
+    fn context(self, msg: impl Into<Cow<'static, str>>) -> core::result::Result<T, ContextError> {
+        self.map_err(|e| e.into().context(msg))
+    }
+
+    fn with_context<F, M>(self, f: F) -> core::result::Result<T, ContextError>
+    where
+        F: FnOnce() -> M,
+        M: Into<Cow<'static, str>>,
+    {
+        self.map_err(|e| e.into().context(f()))
+    }
+}
@@ -0,0 +1,313 @@
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::{cmp, io::BorrowedCursor};
+
+use crate::{BufRead, BufWrite, Error, Read, ReadRef, Result, Seek, SeekFrom, Write};
+
+/// A `Cursor` wraps an in-memory buffer and provides it with a [`Seek`]
+/// implementation.
+///
+/// `Cursor`s are used with in-memory buffers, anything implementing
+/// <code>[AsRef]<\[u8]></code>, to allow them to implement [`Read`] and/or
+/// [`Write`], allowing these buffers to be used anywhere you might use a
+/// reader or writer that does actual I/O.
+///
+/// See [`std::io::Cursor`] for more details.
+#[derive(Debug, Default, Eq, PartialEq, Clone)]
+pub struct Cursor<T> {
+    inner: T,
+    pos: u64,
+}
+
+impl<T> Cursor<T> {
+    /// Creates a new cursor wrapping the provided underlying in-memory buffer.
+    ///
+    /// Cursor initial position is `0` even if underlying buffer (e.g., [`Vec`])
+    /// is not empty. So writing to cursor starts with overwriting [`Vec`]
+    /// content, not with appending to it.
+    ///
+    /// [`Vec`]: alloc::vec::Vec
+    pub const fn new(inner: T) -> Cursor<T> {
+        Cursor { pos: 0, inner }
+    }
+
+    /// Consumes this cursor, returning the underlying value.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Gets a reference to the underlying value in this cursor.
+    pub const fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying value in this cursor.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying value as it may corrupt this cursor's position.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Returns the current position of this cursor.
+    pub const fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Sets the position of this cursor.
+    pub fn set_position(&mut self, pos: u64) {
+        self.pos = pos;
+    }
+}
+
+impl<T: AsRef<[u8]>> Cursor<T> {
+    /// Returns the remaining slice.
+    pub fn remaining_slice(&self) -> &[u8] {
+        let inner = self.inner.as_ref();
+        let len = cmp::min(self.pos, inner.len() as u64) as usize;
+        &inner[len..]
+    }
+
+    /// Returns `true` if the remaining slice is empty.
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.inner.as_ref().len() as u64
+    }
+}
+
+impl<T: AsRef<[u8]>> Seek for Cursor<T> {
+    fn seek(&mut self, style: SeekFrom) -> Result<u64> {
+        self.pos = crate::seek::resolve_seek(style, self.pos, self.inner.as_ref().len() as u64)?;
+        Ok(self.pos)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        Ok(self.inner.as_ref().len() as u64)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+impl<T: AsRef<[u8]>> Read for Cursor<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = Read::read(&mut self.remaining_slice(), buf)?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        let prev_written = cursor.written();
+
+        Read::read_buf(&mut self.remaining_slice(), cursor.reborrow())?;
+
+        self.pos += (cursor.written() - prev_written) as u64;
+
+        Ok(())
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        let result = Read::read_exact(&mut self.remaining_slice(), buf);
+
+        match result {
+            Ok(_) => self.pos += buf.len() as u64,
+            // The only possible error condition is EOF, so place the cursor at "EOF"
+            Err(_) => self.pos = self.inner.as_ref().len() as u64,
+        }
+
+        result
+    }
+
+    fn read_buf_exact(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        let prev_written = cursor.written();
+
+        let result = Read::read_buf_exact(&mut self.remaining_slice(), cursor.reborrow());
+        self.pos += (cursor.written() - prev_written) as u64;
+
+        result
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining_slice().len();
+        (len, Some(len))
+    }
+
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        crate::default_buf_read_to_end_into(self, buf)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        let content = self.remaining_slice();
+        let len = content.len();
+        buf.try_reserve(len).map_err(|_| Error::NoMemory)?;
+        buf.extend_from_slice(content);
+        self.pos += len as u64;
+
+        Ok(len)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        let content = str::from_utf8(self.remaining_slice()).map_err(|_| Error::IllegalBytes)?;
+        let len = content.len();
+        buf.try_reserve(len).map_err(|_| Error::NoMemory)?;
+        buf.push_str(content);
+        self.pos += len as u64;
+
+        Ok(len)
+    }
+}
+
+impl<T: AsRef<[u8]>> BufRead for Cursor<T> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(self.remaining_slice())
+    }
+
+    fn consume(&mut self, amt: usize) {
+        #[cfg(feature = "no-panic")]
+        let amt = cmp::min(amt, self.remaining_slice().len());
+        self.pos += amt as u64;
+    }
+}
+
+impl<'a> ReadRef<'a> for Cursor<&'a [u8]> {
+    fn peek_ref(&self, len: usize) -> Result<&'a [u8]> {
+        let inner: &'a [u8] = self.inner;
+        let start = cmp::min(self.pos, inner.len() as u64) as usize;
+        (&inner[start..]).peek_ref(len)
+    }
+
+    fn read_ref(&mut self, len: usize) -> Result<&'a [u8]> {
+        let data = self.peek_ref(len)?;
+        self.pos += len as u64;
+        Ok(data)
+    }
+
+    fn read_ref_until(&mut self, delim: u8) -> Result<&'a [u8]> {
+        let inner: &'a [u8] = self.inner;
+        let start = cmp::min(self.pos, inner.len() as u64) as usize;
+        let data = (&inner[start..]).read_ref_until(delim)?;
+        self.pos += data.len() as u64;
+        Ok(data)
+    }
+}
+
+// Non-resizing write implementation
+#[inline]
+fn slice_write(pos_mut: &mut u64, slice: &mut [u8], buf: &[u8]) -> Result<usize> {
+    let pos = cmp::min(*pos_mut, slice.len() as u64) as usize;
+    let amt = (&mut slice[pos..]).write(buf)?;
+    *pos_mut += amt as u64;
+    Ok(amt)
+}
+
+/// Resizing write implementation for [`Cursor`].
+///
+/// Cursor is allowed to have a pre-allocated and initialised vector body, but
+/// with a position of 0. This means the [`Write`] will overwrite the contents
+/// of the vec.
+#[cfg(feature = "alloc")]
+fn vec_write(pos_mut: &mut u64, vec: &mut Vec<u8>, buf: &[u8]) -> Result<usize> {
+    let pos = usize::try_from(*pos_mut).map_err(|_| {
+        axerrno::ax_err_type!(
+            InvalidInput,
+            "cursor position exceeds maximum possible vector length"
+        )
+    })?;
+    let end = pos.checked_add(buf.len()).ok_or(Error::InvalidInput)?;
+    vec.try_reserve(end.saturating_sub(vec.len()))
+        .map_err(|_| Error::NoMemory)?;
+
+    // Pad with zeroes up to the current position, like writing past the end
+    // of a file would.
+    if vec.len() < pos {
+        vec.resize(pos, 0);
+    }
+    // Overwrite what is already there, then append the rest.
+    let overlap = cmp::min(vec.len() - pos, buf.len());
+    vec[pos..pos + overlap].copy_from_slice(&buf[..overlap]);
+    vec.extend_from_slice(&buf[overlap..]);
+
+    *pos_mut = end as u64;
+    Ok(buf.len())
+}
+
+impl Write for Cursor<&mut [u8]> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        slice_write(&mut self.pos, self.inner, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl BufWrite for Cursor<&mut [u8]> {
+    #[inline]
+    fn reserve(&mut self, min: usize) -> Result<&mut [u8]> {
+        let pos = cmp::min(self.pos, self.inner.len() as u64) as usize;
+        let spare = &mut self.inner[pos..];
+        if spare.len() < min {
+            return Err(Error::WriteZero);
+        }
+        Ok(spare)
+    }
+
+    #[inline]
+    fn commit(&mut self, n: usize) {
+        let spare = (self.inner.len() as u64).saturating_sub(self.pos);
+        self.pos += cmp::min(n as u64, spare);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Write for Cursor<&mut Vec<u8>> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        vec_write(&mut self.pos, self.inner, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Write for Cursor<Vec<u8>> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        vec_write(&mut self.pos, &mut self.inner, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Write for Cursor<Box<[u8]>> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        slice_write(&mut self.pos, &mut self.inner, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<const N: usize> Write for Cursor<[u8; N]> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        slice_write(&mut self.pos, &mut self.inner, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
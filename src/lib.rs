@@ -14,12 +14,69 @@ pub use axerrno::{AxError as Error, AxErrorKind as ErrorKind, AxResult as Result
 include!(concat!(env!("OUT_DIR"), "/config.rs"));
 
 mod buffered;
+#[cfg(feature = "alloc")]
+mod chunklist;
+#[cfg(feature = "alloc")]
+mod concat;
+mod context;
+mod cursor;
+mod newline;
 pub mod prelude;
 mod read;
+mod scanner;
 mod seek;
+mod tracked;
+mod utf16;
 mod write;
 
-pub use self::{buffered::*, read::*, seek::*, write::*};
+#[cfg(feature = "alloc")]
+pub use self::chunklist::ChunkList;
+#[cfg(feature = "alloc")]
+pub use self::concat::ConcatSeek;
+pub use self::{
+    buffered::*,
+    context::*,
+    cursor::Cursor,
+    newline::{NewlineReader, NewlineWriter},
+    read::*,
+    scanner::Scanner,
+    seek::*,
+    tracked::Tracked,
+    utf16::{Utf16Endian, Utf16Reader, Utf16Writer},
+    write::*,
+};
+
+/// The error returned by [`Read::read_exact_counted`] and
+/// [`Write::write_all_counted`].
+///
+/// It records how many bytes were transferred before the operation failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialTransfer {
+    /// Number of bytes successfully transferred before the failure.
+    pub done: usize,
+    /// The error that stopped the transfer.
+    pub error: Error,
+}
+
+impl PartialTransfer {
+    /// Creates a new `PartialTransfer` from the number of bytes transferred
+    /// and the error that stopped the transfer.
+    pub const fn new(done: usize, error: Error) -> Self {
+        Self { done, error }
+    }
+}
+
+impl From<PartialTransfer> for Error {
+    fn from(e: PartialTransfer) -> Self {
+        e.error
+    }
+}
+
+impl core::fmt::Display for PartialTransfer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} after {} bytes", self.error, self.done)
+    }
+}
 
 /// I/O poll results.
 #[derive(Debug, Default, Clone, Copy)]
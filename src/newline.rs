@@ -0,0 +1,202 @@
+use crate::{BufRead, Error, Read, Result, Write};
+
+/// A reader adapter that converts CRLF and lone CR line endings to LF.
+///
+/// Data without a CR is passed through from the inner reader's buffer without
+/// copying. A CR whose LF only arrives with the next refill is still converted
+/// to a single LF.
+///
+/// [`offset`] counts bytes of the inner stream, so it can be used to report
+/// positions in the original data.
+///
+/// [`offset`]: NewlineReader::offset
+#[derive(Debug)]
+pub struct NewlineReader<R> {
+    inner: R,
+    offset: u64,
+    // The last `fill_buf` returned an LF standing for a CR.
+    at_cr: bool,
+    // A CR was just consumed, so an LF that follows belongs to it.
+    after_cr: bool,
+}
+
+impl<R> NewlineReader<R> {
+    /// Creates a new `NewlineReader<R>` reading from `inner`.
+    pub const fn new(inner: R) -> NewlineReader<R> {
+        NewlineReader {
+            inner,
+            offset: 0,
+            at_cr: false,
+            after_cr: false,
+        }
+    }
+
+    /// Returns the number of bytes consumed from the inner reader so far.
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader are not accounted for.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `NewlineReader<R>`, returning the underlying reader.
+    ///
+    /// If the last byte read was a CR, an LF that follows it is left in the
+    /// underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Read for NewlineReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let nread = {
+            let mut rem = self.fill_buf()?;
+            rem.read(buf)?
+        };
+        self.consume(nread);
+        Ok(nread)
+    }
+}
+
+impl<R: BufRead> BufRead for NewlineReader<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.after_cr {
+            let lf = self.inner.fill_buf()?.first() == Some(&b'\n');
+            self.after_cr = false;
+            if lf {
+                self.inner.consume(1);
+                self.offset += 1;
+            }
+        }
+        let available = self.inner.fill_buf()?;
+        self.at_cr = available.first() == Some(&b'\r');
+        if self.at_cr {
+            return Ok(b"\n");
+        }
+        let end = memchr::memchr(b'\r', available).unwrap_or(available.len());
+        Ok(&available[..end])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if amt == 0 {
+            return;
+        }
+        if self.at_cr {
+            self.inner.consume(1);
+            self.offset += 1;
+            self.at_cr = false;
+            self.after_cr = true;
+        } else {
+            self.inner.consume(amt);
+            self.offset += amt as u64;
+        }
+    }
+}
+
+/// A writer adapter that converts LF line endings to CRLF.
+///
+/// An LF that is already preceded by a CR is left alone, even if the CR was
+/// passed to an earlier `write` call.
+///
+/// [`offset`] counts bytes accepted from the caller, so it reports positions
+/// in the original data rather than in the converted output.
+///
+/// [`offset`]: NewlineWriter::offset
+#[derive(Debug)]
+pub struct NewlineWriter<W> {
+    inner: W,
+    offset: u64,
+    // The last byte accepted was a CR.
+    last_cr: bool,
+    // The CR for the next LF has been written, but the LF itself has not.
+    cr_written: bool,
+}
+
+impl<W> NewlineWriter<W> {
+    /// Creates a new `NewlineWriter<W>` writing to `inner`.
+    pub const fn new(inner: W) -> NewlineWriter<W> {
+        NewlineWriter {
+            inner,
+            offset: 0,
+            last_cr: false,
+            cr_written: false,
+        }
+    }
+
+    /// Returns the number of bytes accepted from the caller so far.
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written directly to the underlying writer are not accounted for.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `NewlineWriter<W>`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for NewlineWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let Some(&first) = buf.first() else {
+            return Ok(0);
+        };
+        if first == b'\n' && !self.last_cr {
+            if !self.cr_written {
+                if self.inner.write(b"\r")? == 0 {
+                    return Err(Error::WriteZero);
+                }
+                self.cr_written = true;
+            }
+            if self.inner.write(b"\n")? == 0 {
+                return Err(Error::WriteZero);
+            }
+            self.cr_written = false;
+            self.offset += 1;
+            return Ok(1);
+        }
+
+        // The caller did not retry the LF whose CR went out, so the next LF
+        // needs a CR of its own.
+        self.cr_written = false;
+
+        // Pass everything up to the next LF that needs a CR straight through.
+        let end = memchr::memchr_iter(b'\n', buf)
+            .find(|&i| i > 0 && buf[i - 1] != b'\r')
+            .unwrap_or(buf.len());
+        let n = self.inner.write(&buf[..end])?;
+        #[cfg(feature = "no-panic")]
+        if n > end {
+            return Err(Error::InvalidData);
+        }
+        if n > 0 {
+            self.last_cr = buf[n - 1] == b'\r';
+            self.offset += n as u64;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
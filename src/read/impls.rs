@@ -2,7 +2,7 @@
 use alloc::{boxed::Box, collections::VecDeque, string::String, vec::Vec};
 use core::{cmp, io::BorrowedCursor};
 
-use crate::{BufRead, Error, Read, Result};
+use crate::{BufRead, BufReadVectored, Error, PartialTransfer, Read, ReadRef, Result};
 
 // =============================================================================
 // Forwarding implementations
@@ -18,6 +18,11 @@ impl<R: Read + ?Sized> Read for &mut R {
         (**self).read_exact(buf)
     }
 
+    #[inline]
+    fn read_exact_counted(&mut self, buf: &mut [u8]) -> core::result::Result<(), PartialTransfer> {
+        (**self).read_exact_counted(buf)
+    }
+
     #[inline]
     fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
         (**self).read_buf(cursor)
@@ -28,6 +33,11 @@ impl<R: Read + ?Sized> Read for &mut R {
         (**self).read_buf_exact(cursor)
     }
 
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+
     #[inline]
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
@@ -39,6 +49,16 @@ impl<R: Read + ?Sized> Read for &mut R {
     fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
         (**self).read_to_string(buf)
     }
+
+    #[inline]
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        (**self).read_to_end_into(buf)
+    }
+
+    #[inline]
+    fn read_to_string_into<'b>(&mut self, buf: &'b mut [u8]) -> Result<&'b str> {
+        (**self).read_to_string_into(buf)
+    }
 }
 
 impl<B: BufRead + ?Sized> BufRead for &mut B {
@@ -52,6 +72,11 @@ impl<B: BufRead + ?Sized> BufRead for &mut B {
         (**self).consume(amt)
     }
 
+    #[inline]
+    fn fill_buf_at_least(&mut self, n: usize) -> Result<&[u8]> {
+        (**self).fill_buf_at_least(n)
+    }
+
     #[inline]
     fn has_data_left(&mut self) -> Result<bool> {
         (**self).has_data_left()
@@ -68,11 +93,79 @@ impl<B: BufRead + ?Sized> BufRead for &mut B {
         (**self).read_until(byte, buf)
     }
 
+    #[inline]
+    fn read_until_into(&mut self, byte: u8, buf: &mut [u8]) -> Result<usize> {
+        (**self).read_until_into(byte, buf)
+    }
+
+    #[inline]
+    fn read_line_into<'b>(&mut self, buf: &'b mut [u8]) -> Result<&'b str> {
+        (**self).read_line_into(buf)
+    }
+
+    #[inline]
+    fn skip_until_any(&mut self, delims: &[u8]) -> Result<usize> {
+        (**self).skip_until_any(delims)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_until_any(&mut self, delims: &[u8], buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_until_any(delims, buf)
+    }
+
+    #[inline]
+    fn skip_until_seq(&mut self, seq: &[u8]) -> Result<usize> {
+        (**self).skip_until_seq(seq)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_until_seq(&mut self, seq: &[u8], buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_until_seq(seq, buf)
+    }
+
     #[inline]
     #[cfg(feature = "alloc")]
     fn read_line(&mut self, buf: &mut String) -> Result<usize> {
         (**self).read_line(buf)
     }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_line_lossy(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_line_lossy(buf)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_until_limited(&mut self, byte: u8, buf: &mut Vec<u8>, max: usize) -> Result<usize> {
+        (**self).read_until_limited(byte, buf, max)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_line_limited(&mut self, buf: &mut String, max: usize) -> Result<usize> {
+        (**self).read_line_limited(buf, max)
+    }
+}
+
+impl<B: BufReadVectored + ?Sized> BufReadVectored for &mut B {
+    #[inline]
+    fn fill_bufs(&mut self) -> Result<[&[u8]; 2]> {
+        (**self).fill_bufs()
+    }
+
+    #[inline]
+    fn skip_until_vectored(&mut self, byte: u8) -> Result<usize> {
+        (**self).skip_until_vectored(byte)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_until_vectored(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_until_vectored(byte, buf)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -87,6 +180,11 @@ impl<R: Read + ?Sized> Read for Box<R> {
         (**self).read_exact(buf)
     }
 
+    #[inline]
+    fn read_exact_counted(&mut self, buf: &mut [u8]) -> core::result::Result<(), PartialTransfer> {
+        (**self).read_exact_counted(buf)
+    }
+
     #[inline]
     fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
         (**self).read_buf(cursor)
@@ -97,6 +195,11 @@ impl<R: Read + ?Sized> Read for Box<R> {
         (**self).read_buf_exact(cursor)
     }
 
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+
     #[inline]
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
@@ -108,6 +211,16 @@ impl<R: Read + ?Sized> Read for Box<R> {
     fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
         (**self).read_to_string(buf)
     }
+
+    #[inline]
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        (**self).read_to_end_into(buf)
+    }
+
+    #[inline]
+    fn read_to_string_into<'b>(&mut self, buf: &'b mut [u8]) -> Result<&'b str> {
+        (**self).read_to_string_into(buf)
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -122,6 +235,11 @@ impl<B: BufRead + ?Sized> BufRead for Box<B> {
         (**self).consume(amt)
     }
 
+    #[inline]
+    fn fill_buf_at_least(&mut self, n: usize) -> Result<&[u8]> {
+        (**self).fill_buf_at_least(n)
+    }
+
     #[inline]
     fn has_data_left(&mut self) -> Result<bool> {
         (**self).has_data_left()
@@ -138,11 +256,79 @@ impl<B: BufRead + ?Sized> BufRead for Box<B> {
         (**self).read_until(byte, buf)
     }
 
+    #[inline]
+    fn read_until_into(&mut self, byte: u8, buf: &mut [u8]) -> Result<usize> {
+        (**self).read_until_into(byte, buf)
+    }
+
+    #[inline]
+    fn read_line_into<'b>(&mut self, buf: &'b mut [u8]) -> Result<&'b str> {
+        (**self).read_line_into(buf)
+    }
+
+    #[inline]
+    fn skip_until_any(&mut self, delims: &[u8]) -> Result<usize> {
+        (**self).skip_until_any(delims)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_until_any(&mut self, delims: &[u8], buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_until_any(delims, buf)
+    }
+
+    #[inline]
+    fn skip_until_seq(&mut self, seq: &[u8]) -> Result<usize> {
+        (**self).skip_until_seq(seq)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_until_seq(&mut self, seq: &[u8], buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_until_seq(seq, buf)
+    }
+
     #[inline]
     #[cfg(feature = "alloc")]
     fn read_line(&mut self, buf: &mut String) -> Result<usize> {
         (**self).read_line(buf)
     }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_line_lossy(&mut self, buf: &mut String) -> Result<usize> {
+        (**self).read_line_lossy(buf)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_until_limited(&mut self, byte: u8, buf: &mut Vec<u8>, max: usize) -> Result<usize> {
+        (**self).read_until_limited(byte, buf, max)
+    }
+
+    #[inline]
+    #[cfg(feature = "alloc")]
+    fn read_line_limited(&mut self, buf: &mut String, max: usize) -> Result<usize> {
+        (**self).read_line_limited(buf, max)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<B: BufReadVectored + ?Sized> BufReadVectored for Box<B> {
+    #[inline]
+    fn fill_bufs(&mut self) -> Result<[&[u8]; 2]> {
+        (**self).fill_bufs()
+    }
+
+    #[inline]
+    fn skip_until_vectored(&mut self, byte: u8) -> Result<usize> {
+        (**self).skip_until_vectored(byte)
+    }
+
+    #[inline]
+    fn read_until_vectored(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+        (**self).read_until_vectored(byte, buf)
+    }
 }
 
 // =============================================================================
@@ -190,6 +376,19 @@ impl Read for &[u8] {
         Ok(())
     }
 
+    #[inline]
+    fn read_exact_counted(&mut self, buf: &mut [u8]) -> core::result::Result<(), PartialTransfer> {
+        if buf.len() > self.len() {
+            // Hand out everything we have so that the caller can see how far
+            // the data went.
+            let done = self.len();
+            buf[..done].copy_from_slice(self);
+            *self = &self[done..];
+            return Err(PartialTransfer::new(done, Error::UnexpectedEof));
+        }
+        self.read_exact(buf).map_err(|e| PartialTransfer::new(0, e))
+    }
+
     #[inline]
     fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
         let amt = cmp::min(cursor.capacity(), self.len());
@@ -217,6 +416,11 @@ impl Read for &[u8] {
         Ok(())
     }
 
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
     #[inline]
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
@@ -237,6 +441,18 @@ impl Read for &[u8] {
         *self = &self[len..];
         Ok(len)
     }
+
+    #[inline]
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let amt = cmp::min(buf.len(), self.len());
+        let (a, b) = self.split_at(amt);
+        buf[..amt].copy_from_slice(a);
+        *self = b;
+        if !self.is_empty() {
+            return Err(Error::OutOfRange);
+        }
+        Ok(amt)
+    }
 }
 
 impl BufRead for &[u8] {
@@ -247,8 +463,47 @@ impl BufRead for &[u8] {
 
     #[inline]
     fn consume(&mut self, amt: usize) {
+        #[cfg(feature = "no-panic")]
+        let amt = cmp::min(amt, self.len());
         *self = &self[amt..];
     }
+
+    #[inline]
+    fn fill_buf_at_least(&mut self, n: usize) -> Result<&[u8]> {
+        if self.len() < n {
+            return Err(Error::UnexpectedEof);
+        }
+        Ok(*self)
+    }
+}
+
+impl<'a> ReadRef<'a> for &'a [u8] {
+    #[inline]
+    fn peek_ref(&self, len: usize) -> Result<&'a [u8]> {
+        self.get(..len).ok_or(Error::UnexpectedEof)
+    }
+
+    #[inline]
+    fn read_ref(&mut self, len: usize) -> Result<&'a [u8]> {
+        let (a, b) = self.split_at_checked(len).ok_or(Error::UnexpectedEof)?;
+        *self = b;
+        Ok(a)
+    }
+
+    #[inline]
+    fn read_ref_until(&mut self, delim: u8) -> Result<&'a [u8]> {
+        let len = memchr::memchr(delim, self).map_or(self.len(), |i| i + 1);
+        let (a, b) = self.split_at(len);
+        *self = b;
+        Ok(a)
+    }
+}
+
+impl BufReadVectored for &[u8] {
+    #[inline]
+    fn fill_bufs(&mut self) -> Result<[&[u8]; 2]> {
+        Ok([*self, &[]])
+    }
 }
 
 #[cfg(feature = "alloc")]
@@ -285,6 +540,20 @@ impl Read for VecDeque<u8> {
         Ok(())
     }
 
+    #[inline]
+    fn read_exact_counted(&mut self, buf: &mut [u8]) -> core::result::Result<(), PartialTransfer> {
+        let done = self.len();
+        if buf.len() > done {
+            let (front, back) = self.as_slices();
+            let (buf_front, buf_back) = buf[..done].split_at_mut(front.len());
+            buf_front.copy_from_slice(front);
+            buf_back.copy_from_slice(back);
+            self.clear();
+            return Err(PartialTransfer::new(done, Error::UnexpectedEof));
+        }
+        self.read_exact(buf).map_err(|e| PartialTransfer::new(0, e))
+    }
+
     #[inline]
     fn read_buf(&mut self, cursor: BorrowedCursor<'_>) -> Result<()> {
         let (ref mut front, _) = self.as_slices();
@@ -318,6 +587,16 @@ impl Read for VecDeque<u8> {
         Ok(())
     }
 
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len(), Some(self.len()))
+    }
+
+    #[inline]
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        crate::default_buf_read_to_end_into(self, buf)
+    }
+
     #[inline]
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
@@ -352,6 +631,39 @@ impl BufRead for VecDeque<u8> {
 
     #[inline]
     fn consume(&mut self, amt: usize) {
+        #[cfg(feature = "no-panic")]
+        let amt = cmp::min(amt, self.len());
         self.drain(..amt);
     }
+
+    #[inline]
+    fn fill_buf_at_least(&mut self, n: usize) -> Result<&[u8]> {
+        if self.len() < n {
+            return Err(Error::UnexpectedEof);
+        }
+        if self.as_slices().0.len() < n {
+            self.make_contiguous();
+        }
+        Ok(self.as_slices().0)
+    }
+
+    // Search both halves of the deque instead of stopping at the wrap-around.
+    #[inline]
+    fn skip_until(&mut self, byte: u8) -> Result<usize> {
+        self.skip_until_vectored(byte)
+    }
+
+    #[inline]
+    fn read_until(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+        self.read_until_vectored(byte, buf)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BufReadVectored for VecDeque<u8> {
+    #[inline]
+    fn fill_bufs(&mut self) -> Result<[&[u8]; 2]> {
+        let (front, back) = self.as_slices();
+        Ok([front, back])
+    }
 }
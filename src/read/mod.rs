@@ -2,28 +2,38 @@
 use alloc::{string::String, vec::Vec};
 use core::io::BorrowedCursor;
 
-use crate::{Error, Result};
+use crate::{Error, PartialTransfer, Result};
 
 mod impls;
+mod utf8;
+
+pub use self::utf8::{Chars, Utf8Reader};
 
 /// Default [`Read::read_exact`] implementation.
-pub fn default_read_exact<R: Read + ?Sized>(this: &mut R, mut buf: &mut [u8]) -> Result<()> {
-    while !buf.is_empty() {
-        match this.read(buf) {
-            Ok(0) => break,
-            Ok(n) => {
-                buf = &mut buf[n..];
+pub fn default_read_exact<R: Read + ?Sized>(this: &mut R, buf: &mut [u8]) -> Result<()> {
+    default_read_exact_counted(this, buf).map_err(Into::into)
+}
+
+/// Default [`Read::read_exact_counted`] implementation.
+pub fn default_read_exact_counted<R: Read + ?Sized>(
+    this: &mut R,
+    buf: &mut [u8],
+) -> core::result::Result<(), PartialTransfer> {
+    let mut done = 0;
+    while done < buf.len() {
+        match this.read(&mut buf[done..]) {
+            Ok(0) => return Err(PartialTransfer::new(done, Error::UnexpectedEof)),
+            #[cfg(feature = "no-panic")]
+            Ok(n) if n > buf.len() - done => {
+                return Err(PartialTransfer::new(done, Error::InvalidData));
             }
+            Ok(n) => done += n,
             #[cfg(feature = "continue-on-interrupt")]
             Err(e) if e.canonicalize() == Error::Interrupted => continue,
-            Err(e) => return Err(e),
+            Err(e) => return Err(PartialTransfer::new(done, e)),
         }
     }
-    if !buf.is_empty() {
-        Err(Error::UnexpectedEof)
-    } else {
-        Ok(())
-    }
+    Ok(())
 }
 
 /// Default [`Read::read_buf`] implementation.
@@ -34,12 +44,20 @@ where
     #[cfg(borrowedbuf_init)]
     {
         let n = read(cursor.ensure_init().init_mut())?;
+        #[cfg(feature = "no-panic")]
+        if n > cursor.capacity() {
+            return Err(Error::InvalidData);
+        }
         cursor.advance(n);
     }
     #[cfg(not(borrowedbuf_init))]
     {
         // SAFETY: We do not uninitialize any part of the buffer.
         let n = read(unsafe { cursor.as_mut().write_filled(0) })?;
+        #[cfg(feature = "no-panic")]
+        if n > cursor.capacity() {
+            return Err(Error::InvalidData);
+        }
         assert!(n <= cursor.capacity());
         // SAFETY: We've initialized the entire buffer, and `read` can't make it uninitialized.
         unsafe {
@@ -98,12 +116,14 @@ pub fn default_read_to_end<R: Read + ?Sized>(
     fn small_probe_read<R: Read + ?Sized>(r: &mut R, buf: &mut Vec<u8>) -> Result<usize> {
         let mut probe = [0u8; PROBE_SIZE];
 
+        // Reserve before reading: there is no way to recover from allocation
+        // failure once the data has been read.
+        buf.try_reserve(PROBE_SIZE).map_err(|_| Error::NoMemory)?;
+
         #[allow(clippy::never_loop)]
         loop {
             match r.read(&mut probe) {
                 Ok(n) => {
-                    // there is no way to recover from allocation failure here
-                    // because the data has already been read.
                     buf.extend_from_slice(&probe[..n]);
                     return Ok(n);
                 }
@@ -256,6 +276,62 @@ where
     }
 }
 
+/// Default [`Read::read_to_end_into`] implementation.
+pub fn default_read_to_end_into<R: Read + ?Sized>(r: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    loop {
+        if filled >= buf.len() {
+            // The buffer is full, so probe for EOF.
+            let mut probe = [0u8; 1];
+            match r.read(&mut probe) {
+                Ok(0) => return Ok(filled),
+                Ok(_) => return Err(Error::OutOfRange),
+                #[cfg(feature = "continue-on-interrupt")]
+                Err(e) if e.canonicalize() == Error::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+        match r.read(&mut buf[filled..]) {
+            Ok(0) => return Ok(filled),
+            #[cfg(feature = "no-panic")]
+            Ok(n) if n > buf.len() - filled => return Err(Error::InvalidData),
+            Ok(n) => filled += n,
+            #[cfg(feature = "continue-on-interrupt")]
+            Err(e) if e.canonicalize() == Error::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// [`Read::read_to_end_into`] implementation for [`BufRead`] types.
+///
+/// Whether the data fits in `buf` is decided by looking at the internal
+/// buffer, so unlike [`default_read_to_end_into`], no byte is lost if it does
+/// not: reading can continue where `buf` ends.
+pub fn default_buf_read_to_end_into<R: BufRead + ?Sized>(
+    r: &mut R,
+    buf: &mut [u8],
+) -> Result<usize> {
+    let mut filled = 0;
+    loop {
+        let src = match r.fill_buf() {
+            Ok([]) => return Ok(filled),
+            Ok(src) => src,
+            #[cfg(feature = "continue-on-interrupt")]
+            Err(e) if e.canonicalize() == Error::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        let rest = buf.get_mut(filled..).unwrap_or_default();
+        if rest.is_empty() {
+            return Err(Error::OutOfRange);
+        }
+        let n = core::cmp::min(src.len(), rest.len());
+        rest[..n].copy_from_slice(&src[..n]);
+        r.consume(n);
+        filled += n;
+    }
+}
+
 /// Default [`Read::read_to_string`] implementation with optional size hint.
 #[cfg(feature = "alloc")]
 pub fn default_read_to_string<R: Read + ?Sized>(
@@ -288,6 +364,15 @@ pub trait Read {
         default_read_exact(self, buf)
     }
 
+    /// Read the exact number of bytes required to fill `buf`, reporting how
+    /// many bytes were read if it fails.
+    ///
+    /// On failure, the first [`PartialTransfer::done`] bytes of `buf` contain
+    /// the data that was read before the error occurred.
+    fn read_exact_counted(&mut self, buf: &mut [u8]) -> core::result::Result<(), PartialTransfer> {
+        default_read_exact_counted(self, buf)
+    }
+
     /// Pull some bytes from this source into the specified buffer.
     ///
     /// This method makes it possible to return both data and an error but it is advised against.
@@ -302,16 +387,67 @@ pub trait Read {
         default_read_buf_exact(self, cursor)
     }
 
+    /// Returns the bounds on the number of bytes left in this source.
+    ///
+    /// Like [`Iterator::size_hint`], this returns a lower bound and an optional
+    /// upper bound. It is used by [`read_to_end`] and [`read_to_string`] to
+    /// size their buffers up front.
+    ///
+    /// The default implementation returns `(0, None)`.
+    ///
+    /// [`read_to_end`]: Read::read_to_end
+    /// [`read_to_string`]: Read::read_to_string
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, None)
+    }
+
     /// Read all bytes until EOF in this source, placing them into `buf`.
     #[cfg(feature = "alloc")]
     fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
-        default_read_to_end(self, buf, None)
+        // The upper bound is not a promise, so only rely on it when it is
+        // exact.
+        let (lower, upper) = self.size_hint();
+        let exact = upper.filter(|&upper| upper == lower);
+        buf.try_reserve(lower).map_err(|_| Error::NoMemory)?;
+        default_read_to_end(self, buf, exact)
     }
 
     /// Read all bytes until EOF in this source, appending them to `buf`.
     #[cfg(feature = "alloc")]
     fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
-        default_read_to_string(self, buf, None)
+        // The upper bound is not a promise, so only rely on it when it is
+        // exact.
+        let (lower, upper) = self.size_hint();
+        let exact = upper.filter(|&upper| upper == lower);
+        buf.try_reserve(lower).map_err(|_| Error::NoMemory)?;
+        default_read_to_string(self, buf, exact)
+    }
+
+    /// Read all bytes until EOF in this source into `buf`, without allocating.
+    ///
+    /// Returns the number of bytes read. If the source holds more than
+    /// `buf.len()` bytes, `buf` is filled and [`Error::OutOfRange`] is
+    /// returned. The default implementation has to read one byte past the
+    /// end of `buf` to find that out; that byte is lost. Buffered readers
+    /// override it with [`default_buf_read_to_end_into`], which keeps it.
+    fn read_to_end_into(&mut self, buf: &mut [u8]) -> Result<usize> {
+        default_read_to_end_into(self, buf)
+    }
+
+    /// Read all bytes until EOF in this source into `buf`, without allocating,
+    /// and returns them as a string slice.
+    ///
+    /// Overlong input is handled as in [`read_to_end_into`]. If the data is
+    /// not valid UTF-8, [`Error::IllegalBytes`] is returned.
+    ///
+    /// [`read_to_end_into`]: Read::read_to_end_into
+    fn read_to_string_into<'b>(&mut self, buf: &'b mut [u8]) -> Result<&'b str> {
+        let n = self.read_to_end_into(buf)?;
+        #[cfg(feature = "no-panic")]
+        if n > buf.len() {
+            return Err(Error::InvalidData);
+        }
+        str::from_utf8(&buf[..n]).map_err(|_| Error::IllegalBytes)
     }
 
     /// Creates a "by reference" adapter for this instance of `Read`.
@@ -326,6 +462,30 @@ pub trait Read {
     }
 }
 
+/// A `ReadRef` is a [`Read`]er over data that already lives in memory for the
+/// lifetime `'a`, allowing it to be borrowed instead of copied.
+///
+/// Generic parsers can bound on `ReadRef` to borrow fields directly, and fall
+/// back to copying through [`Read`] for other sources.
+///
+/// Unlike [`Read::read_exact`], none of these methods advance the reader when
+/// they fail.
+pub trait ReadRef<'a>: Read {
+    /// Returns the next `len` bytes without advancing the reader.
+    ///
+    /// Returns [`Error::UnexpectedEof`] if fewer than `len` bytes are left.
+    fn peek_ref(&self, len: usize) -> Result<&'a [u8]>;
+
+    /// Returns the next `len` bytes and advances the reader past them.
+    ///
+    /// Returns [`Error::UnexpectedEof`] if fewer than `len` bytes are left.
+    fn read_ref(&mut self, len: usize) -> Result<&'a [u8]>;
+
+    /// Returns all bytes up to and including the delimiter `delim`, or up to
+    /// EOF if it is not found, and advances the reader past them.
+    fn read_ref_until(&mut self, delim: u8) -> Result<&'a [u8]>;
+}
+
 /// Reads all bytes from a [reader][Read] into a new [`String`].
 ///
 /// This is a convenience function for [`Read::read_to_string`].
@@ -338,6 +498,58 @@ pub fn read_to_string<R: Read>(mut reader: R) -> Result<String> {
     Ok(buf)
 }
 
+/// Returns the index of the first byte in `haystack` that is one of `delims`.
+fn memchr_any(delims: &[u8], haystack: &[u8]) -> Option<usize> {
+    match *delims {
+        [] => None,
+        [a] => memchr::memchr(a, haystack),
+        [a, b] => memchr::memchr2(a, b, haystack),
+        [a, b, c] => memchr::memchr3(a, b, c, haystack),
+        _ => haystack.iter().position(|b| delims.contains(b)),
+    }
+}
+
+/// Searches `chunk` for the end of `finder`'s needle, given that the last
+/// `*matched` bytes before `chunk` are the start of the needle.
+///
+/// Returns how many bytes of `chunk` belong to the stream up to and including
+/// the needle. If it is not found, `*matched` is updated for the next chunk.
+fn find_seq(
+    finder: &memchr::memmem::Finder<'_>,
+    matched: &mut usize,
+    chunk: &[u8],
+) -> Option<usize> {
+    let seq = finder.needle();
+    // A match that started in an earlier chunk. The longest candidate starts
+    // the earliest, so try that first.
+    for j in (1..=*matched).rev() {
+        if seq[*matched - j..*matched] != seq[..j] {
+            continue;
+        }
+        let rest = &seq[j..];
+        if chunk.len() >= rest.len() {
+            if chunk.starts_with(rest) {
+                *matched = 0;
+                return Some(rest.len());
+            }
+        } else if rest.starts_with(chunk) {
+            *matched = j + chunk.len();
+            return None;
+        }
+    }
+    if let Some(i) = finder.find(chunk) {
+        *matched = 0;
+        return Some(i + seq.len());
+    }
+    // Remember the longest tail of `chunk` that may start a match.
+    let max = core::cmp::min(seq.len().saturating_sub(1), chunk.len());
+    *matched = (1..=max)
+        .rev()
+        .find(|&l| chunk.ends_with(&seq[..l]))
+        .unwrap_or(0);
+    None
+}
+
 /// A `BufRead` is a type of `Read`er which has an internal buffer, allowing it
 /// to perform extra ways of reading.
 ///
@@ -351,6 +563,29 @@ pub trait BufRead: Read {
     /// Subsequent calls to `read` only return bytes that have not been marked as read.
     fn consume(&mut self, amount: usize);
 
+    /// Returns the contents of the internal buffer, making sure it holds at
+    /// least `n` contiguous bytes.
+    ///
+    /// Returns [`Error::UnexpectedEof`] if EOF is reached before `n` bytes are
+    /// available. Readers that cannot provide `n` contiguous bytes for other
+    /// reasons, such as a buffer that is too small, return a different error.
+    ///
+    /// The default implementation cannot refill a partially consumed buffer,
+    /// so it returns [`Error::Unsupported`] if [`fill_buf`] returns fewer than
+    /// `n` bytes but is not at EOF.
+    ///
+    /// [`fill_buf`]: BufRead::fill_buf
+    fn fill_buf_at_least(&mut self, n: usize) -> Result<&[u8]> {
+        let buf = self.fill_buf()?;
+        if buf.len() >= n {
+            Ok(buf)
+        } else if buf.is_empty() {
+            Err(Error::UnexpectedEof)
+        } else {
+            Err(Error::Unsupported)
+        }
+    }
+
     /// Checks if there is any data left to be `read`.
     fn has_data_left(&mut self) -> Result<bool> {
         self.fill_buf().map(|b| !b.is_empty())
@@ -384,10 +619,13 @@ pub trait BufRead: Read {
                 let available = self.fill_buf()?;
                 match memchr::memchr(byte, available) {
                     Some(i) => {
+                        buf.try_reserve(i + 1).map_err(|_| Error::NoMemory)?;
                         buf.extend_from_slice(&available[..=i]);
                         (true, i + 1)
                     }
                     None => {
+                        buf.try_reserve(available.len())
+                            .map_err(|_| Error::NoMemory)?;
                         buf.extend_from_slice(available);
                         (false, available.len())
                     }
@@ -401,6 +639,206 @@ pub trait BufRead: Read {
         }
     }
 
+    /// Skips all bytes until any of the bytes in `delims` or EOF is reached.
+    ///
+    /// The delimiter found is skipped as well. Nothing matches if `delims` is
+    /// empty.
+    fn skip_until_any(&mut self, delims: &[u8]) -> Result<usize> {
+        let mut read = 0;
+        loop {
+            let (done, used) = {
+                let available = self.fill_buf()?;
+                match memchr_any(delims, available) {
+                    Some(i) => (true, i + 1),
+                    None => (false, available.len()),
+                }
+            };
+            self.consume(used);
+            read += used;
+            if done || used == 0 {
+                return Ok(read);
+            }
+        }
+    }
+
+    /// Read all bytes into `buf` until any of the bytes in `delims` or EOF is
+    /// reached.
+    ///
+    /// The delimiter found, if any, is appended to `buf` as well. Nothing
+    /// matches if `delims` is empty.
+    #[cfg(feature = "alloc")]
+    fn read_until_any(&mut self, delims: &[u8], buf: &mut Vec<u8>) -> Result<usize> {
+        let mut read = 0;
+        loop {
+            let (done, used) = {
+                let available = self.fill_buf()?;
+                let (done, used) = match memchr_any(delims, available) {
+                    Some(i) => (true, i + 1),
+                    None => (false, available.len()),
+                };
+                buf.try_reserve(used).map_err(|_| Error::NoMemory)?;
+                buf.extend_from_slice(&available[..used]);
+                (done, used)
+            };
+            self.consume(used);
+            read += used;
+            if done || used == 0 {
+                return Ok(read);
+            }
+        }
+    }
+
+    /// Skips all bytes until the byte sequence `seq` or EOF is reached.
+    ///
+    /// `seq` is skipped as well, even if it spans more than one buffer fill.
+    /// An empty `seq` matches immediately.
+    fn skip_until_seq(&mut self, seq: &[u8]) -> Result<usize> {
+        if seq.is_empty() {
+            return Ok(0);
+        }
+        let finder = memchr::memmem::Finder::new(seq);
+        let mut matched = 0;
+        let mut read = 0;
+        loop {
+            let (done, used) = {
+                let available = self.fill_buf()?;
+                match find_seq(&finder, &mut matched, available) {
+                    Some(n) => (true, n),
+                    None => (false, available.len()),
+                }
+            };
+            self.consume(used);
+            read += used;
+            if done || used == 0 {
+                return Ok(read);
+            }
+        }
+    }
+
+    /// Read all bytes into `buf` until the byte sequence `seq` or EOF is
+    /// reached.
+    ///
+    /// `seq` is appended to `buf` as well, even if it spans more than one
+    /// buffer fill. An empty `seq` matches immediately.
+    #[cfg(feature = "alloc")]
+    fn read_until_seq(&mut self, seq: &[u8], buf: &mut Vec<u8>) -> Result<usize> {
+        if seq.is_empty() {
+            return Ok(0);
+        }
+        let finder = memchr::memmem::Finder::new(seq);
+        let mut matched = 0;
+        let mut read = 0;
+        loop {
+            let (done, used) = {
+                let available = self.fill_buf()?;
+                let (done, used) = match find_seq(&finder, &mut matched, available) {
+                    Some(n) => (true, n),
+                    None => (false, available.len()),
+                };
+                buf.try_reserve(used).map_err(|_| Error::NoMemory)?;
+                buf.extend_from_slice(&available[..used]);
+                (done, used)
+            };
+            self.consume(used);
+            read += used;
+            if done || used == 0 {
+                return Ok(read);
+            }
+        }
+    }
+
+    /// Read all bytes into `buf` until the delimiter `byte` or EOF is reached,
+    /// appending at most `max` bytes.
+    ///
+    /// The limit includes the delimiter. If `max` bytes have been appended
+    /// without finding the delimiter and the reader is not at EOF,
+    /// [`Error::OutOfRange`] is returned. The bytes read so far are left in
+    /// `buf`, and nothing past the limit is consumed, so the caller can skip
+    /// the rest of the record with [`skip_until`].
+    ///
+    /// [`skip_until`]: BufRead::skip_until
+    #[cfg(feature = "alloc")]
+    fn read_until_limited(&mut self, byte: u8, buf: &mut Vec<u8>, max: usize) -> Result<usize> {
+        let mut read = 0;
+        loop {
+            let (done, used) = {
+                let available = self.fill_buf()?;
+                if available.is_empty() {
+                    return Ok(read);
+                }
+                if read == max {
+                    return Err(Error::OutOfRange);
+                }
+                let available = &available[..core::cmp::min(available.len(), max - read)];
+                let (done, used) = match memchr::memchr(byte, available) {
+                    Some(i) => (true, i + 1),
+                    None => (false, available.len()),
+                };
+                buf.try_reserve(used).map_err(|_| Error::NoMemory)?;
+                buf.extend_from_slice(&available[..used]);
+                (done, used)
+            };
+            self.consume(used);
+            read += used;
+            if done {
+                return Ok(read);
+            }
+        }
+    }
+
+    /// Read all bytes into `buf` until the delimiter `byte` or EOF is reached,
+    /// without allocating.
+    ///
+    /// Returns the number of bytes written to `buf`, including the delimiter.
+    /// If `buf` fills up before the delimiter is found and the reader is not
+    /// at EOF, [`Error::OutOfRange`] is returned. In that case `buf` holds the
+    /// first `buf.len()` bytes of the record, and nothing past them is
+    /// consumed.
+    fn read_until_into(&mut self, byte: u8, buf: &mut [u8]) -> Result<usize> {
+        let mut read = 0;
+        loop {
+            let (done, used) = {
+                let available = self.fill_buf()?;
+                if available.is_empty() {
+                    return Ok(read);
+                }
+                if read >= buf.len() {
+                    return Err(Error::OutOfRange);
+                }
+                let rest = &mut buf[read..];
+                let available = &available[..core::cmp::min(available.len(), rest.len())];
+                let (done, used) = match memchr::memchr(byte, available) {
+                    Some(i) => (true, i + 1),
+                    None => (false, available.len()),
+                };
+                rest[..used].copy_from_slice(&available[..used]);
+                (done, used)
+            };
+            self.consume(used);
+            read += used;
+            if done {
+                return Ok(read);
+            }
+        }
+    }
+
+    /// Read all bytes until a newline (the `0xA` byte) is reached into `buf`,
+    /// without allocating, and returns them as a string slice.
+    ///
+    /// The newline is included. Overlong lines are handled as in
+    /// [`read_until_into`]. If the line is not valid UTF-8,
+    /// [`Error::IllegalBytes`] is returned.
+    ///
+    /// [`read_until_into`]: BufRead::read_until_into
+    fn read_line_into<'b>(&mut self, buf: &'b mut [u8]) -> Result<&'b str> {
+        let n = self.read_until_into(b'\n', buf)?;
+        #[cfg(feature = "no-panic")]
+        if n > buf.len() {
+            return Err(Error::InvalidData);
+        }
+        str::from_utf8(&buf[..n]).map_err(|_| Error::IllegalBytes)
+    }
+
     /// Read all bytes until a newline (the `0xA` byte) is reached, and append
     /// them to the provided `String` buffer.
     #[cfg(feature = "alloc")]
@@ -408,6 +846,54 @@ pub trait BufRead: Read {
         unsafe { super::append_to_string(buf, |b| self.read_until(b'\n', b)) }
     }
 
+    /// Read all bytes until a newline (the `0xA` byte) is reached, appending
+    /// at most `max` bytes to the provided `String` buffer.
+    ///
+    /// See [`read_until_limited`] for how the limit is handled. As with
+    /// [`read_line`], nothing is appended to `buf` if the bytes read are not
+    /// valid UTF-8, which includes a character cut in half by the limit.
+    ///
+    /// [`read_until_limited`]: BufRead::read_until_limited
+    /// [`read_line`]: BufRead::read_line
+    #[cfg(feature = "alloc")]
+    fn read_line_limited(&mut self, buf: &mut String, max: usize) -> Result<usize> {
+        unsafe { super::append_to_string(buf, |b| self.read_until_limited(b'\n', b, max)) }
+    }
+
+    /// Read all bytes until a newline (the `0xA` byte) is reached, and append
+    /// them to the provided `String` buffer, replacing invalid UTF-8 sequences
+    /// with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// Returns the number of bytes read, which may differ from the number of
+    /// bytes appended. If an error occurs, the data read so far is still
+    /// appended.
+    #[cfg(feature = "alloc")]
+    fn read_line_lossy(&mut self, buf: &mut String) -> Result<usize> {
+        let mut bytes = Vec::new();
+        let res = self.read_until(b'\n', &mut bytes);
+        for chunk in bytes.utf8_chunks() {
+            buf.try_reserve(chunk.valid().len() + 3)
+                .map_err(|_| Error::NoMemory)?;
+            buf.push_str(chunk.valid());
+            if !chunk.invalid().is_empty() {
+                buf.push(char::REPLACEMENT_CHARACTER);
+            }
+        }
+        res
+    }
+
+    /// Returns an iterator over the `char`s of this reader.
+    ///
+    /// Characters are decoded as UTF-8, and may straddle refills of the
+    /// internal buffer. An invalid sequence yields [`Error::IllegalBytes`]
+    /// once, after which decoding goes on with the next byte.
+    fn chars(self) -> Chars<Self>
+    where
+        Self: Sized,
+    {
+        Chars { inner: self }
+    }
+
     /// Returns an iterator over the contents of this reader split on the byte
     /// `byte`.
     #[cfg(feature = "alloc")]
@@ -421,13 +907,202 @@ pub trait BufRead: Read {
         }
     }
 
+    /// Returns an iterator over the contents of this reader split on the byte
+    /// sequence `seq`.
+    ///
+    /// The separators are not included in the items. An empty `seq` yields
+    /// no items.
+    #[cfg(feature = "alloc")]
+    fn split_seq(self, seq: &[u8]) -> SplitSeq<'_, Self>
+    where
+        Self: Sized,
+    {
+        SplitSeq {
+            buf: self,
+            delim: seq,
+        }
+    }
+
     /// Returns an iterator over the lines of this reader.
     #[cfg(feature = "alloc")]
     fn lines(self) -> Lines<Self>
     where
         Self: Sized,
     {
-        Lines { buf: self }
+        Lines {
+            buf: self,
+            max_len: None,
+            skip_rest: false,
+        }
+    }
+
+    /// Returns an iterator over the lines of this reader, replacing invalid
+    /// UTF-8 sequences with U+FFFD REPLACEMENT CHARACTER.
+    ///
+    /// See [`read_line_lossy`].
+    ///
+    /// [`read_line_lossy`]: BufRead::read_line_lossy
+    #[cfg(feature = "alloc")]
+    fn lines_lossy(self) -> LinesLossy<Self>
+    where
+        Self: Sized,
+    {
+        LinesLossy { buf: self }
+    }
+
+    /// Calls `f` on each record of this reader split on the byte `byte`.
+    ///
+    /// This is the borrowing counterpart of [`split`]: a record that lies
+    /// entirely within the internal buffer is passed to `f` straight from
+    /// [`fill_buf`]. Only records that span a refill are copied into a scratch
+    /// buffer, which is reused.
+    ///
+    /// The delimiter is not included in the records. Iteration stops at EOF or
+    /// at the first error returned by `f`, which is passed on. Records handed
+    /// to `f` have been consumed, even if `f` fails.
+    ///
+    /// [`split`]: BufRead::split
+    /// [`fill_buf`]: BufRead::fill_buf
+    #[cfg(feature = "alloc")]
+    fn for_each_split<F>(&mut self, byte: u8, mut f: F) -> Result<()>
+    where
+        Self: Sized,
+        F: FnMut(&[u8]) -> Result<()>,
+    {
+        let mut scratch = Vec::new();
+        loop {
+            let (used, res) = {
+                let available = self.fill_buf()?;
+                if available.is_empty() {
+                    return if scratch.is_empty() {
+                        Ok(())
+                    } else {
+                        f(&scratch)
+                    };
+                }
+                match memchr::memchr(byte, available) {
+                    Some(i) if scratch.is_empty() => (i + 1, f(&available[..i])),
+                    Some(i) => {
+                        scratch.try_reserve(i).map_err(|_| Error::NoMemory)?;
+                        scratch.extend_from_slice(&available[..i]);
+                        let res = f(&scratch);
+                        scratch.clear();
+                        (i + 1, res)
+                    }
+                    None => {
+                        scratch
+                            .try_reserve(available.len())
+                            .map_err(|_| Error::NoMemory)?;
+                        scratch.extend_from_slice(available);
+                        (available.len(), Ok(()))
+                    }
+                }
+            };
+            self.consume(used);
+            res?;
+        }
+    }
+
+    /// Calls `f` on each line of this reader.
+    ///
+    /// This is the borrowing counterpart of [`lines`], see [`for_each_split`]
+    /// for how lines are buffered. The `\n` or `\r\n` line ending is not
+    /// included. A line that is not valid UTF-8 stops the iteration with
+    /// [`Error::IllegalBytes`].
+    ///
+    /// [`lines`]: BufRead::lines
+    /// [`for_each_split`]: BufRead::for_each_split
+    #[cfg(feature = "alloc")]
+    fn for_each_line<F>(&mut self, mut f: F) -> Result<()>
+    where
+        Self: Sized,
+        F: FnMut(&str) -> Result<()>,
+    {
+        self.for_each_split(b'\n', |line| {
+            let line = line.strip_suffix(b"\r").unwrap_or(line);
+            f(str::from_utf8(line).map_err(|_| Error::IllegalBytes)?)
+        })
+    }
+}
+
+/// A `BufReadVectored` is a [`BufRead`] whose internal buffer may consist of
+/// more than one contiguous segment, such as a ring buffer.
+///
+/// Unlike [`fill_buf`], which only returns the first segment,
+/// [`fill_bufs`] exposes all of the buffered data at once. [`consume`] spans
+/// segments: consuming more than the length of the first segment continues
+/// into the second one.
+///
+/// [`fill_buf`]: BufRead::fill_buf
+/// [`fill_bufs`]: BufReadVectored::fill_bufs
+/// [`consume`]: BufRead::consume
+pub trait BufReadVectored: BufRead {
+    /// Returns all segments of the internal buffer, in order, filling it with
+    /// more data if it is empty.
+    ///
+    /// The second segment is empty if the buffered data is contiguous. Both
+    /// are empty at EOF.
+    fn fill_bufs(&mut self) -> Result<[&[u8]; 2]>;
+
+    /// Skips all bytes until the delimiter `byte` or EOF is reached, searching
+    /// across all buffered segments at once.
+    fn skip_until_vectored(&mut self, byte: u8) -> Result<usize> {
+        let mut read = 0;
+        loop {
+            let (done, used) = {
+                let mut used = 0;
+                let mut done = false;
+                for seg in self.fill_bufs()? {
+                    if let Some(i) = memchr::memchr(byte, seg) {
+                        used += i + 1;
+                        done = true;
+                        break;
+                    }
+                    used += seg.len();
+                }
+                (done, used)
+            };
+            self.consume(used);
+            read += used;
+            if done || used == 0 {
+                return Ok(read);
+            }
+        }
+    }
+
+    /// Read all bytes into `buf` until the delimiter `byte` or EOF is reached,
+    /// searching across all buffered segments at once.
+    #[cfg(feature = "alloc")]
+    fn read_until_vectored(&mut self, byte: u8, buf: &mut Vec<u8>) -> Result<usize> {
+        let mut read = 0;
+        loop {
+            let (done, used) = {
+                let segs = self.fill_bufs()?;
+                let mut found = None;
+                let mut total = 0;
+                for seg in segs {
+                    if let Some(i) = memchr::memchr(byte, seg) {
+                        found = Some(total + i + 1);
+                        break;
+                    }
+                    total += seg.len();
+                }
+                let used = found.unwrap_or(total);
+                buf.try_reserve(used).map_err(|_| Error::NoMemory)?;
+                let mut rem = used;
+                for seg in segs {
+                    let n = core::cmp::min(rem, seg.len());
+                    buf.extend_from_slice(&seg[..n]);
+                    rem -= n;
+                }
+                (found.is_some(), used)
+            };
+            self.consume(used);
+            read += used;
+            if done || used == 0 {
+                return Ok(read);
+            }
+        }
     }
 }
 
@@ -454,7 +1129,7 @@ impl<B: BufRead> Iterator for Split<B> {
         match self.buf.read_until(self.delim, &mut buf) {
             Ok(0) => None,
             Ok(_n) => {
-                if buf[buf.len() - 1] == self.delim {
+                if buf.last() == Some(&self.delim) {
                     buf.pop();
                 }
                 Some(Ok(buf))
@@ -464,6 +1139,39 @@ impl<B: BufRead> Iterator for Split<B> {
     }
 }
 
+/// An iterator over the contents of an instance of `BufRead` split on a
+/// particular byte sequence.
+///
+/// This struct is generally created by calling [`split_seq`] on a `BufRead`.
+/// Please see the documentation of [`split_seq`] for more details.
+///
+/// [`split_seq`]: BufRead::split_seq
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct SplitSeq<'a, B> {
+    buf: B,
+    delim: &'a [u8],
+}
+
+#[cfg(feature = "alloc")]
+impl<B: BufRead> Iterator for SplitSeq<'_, B> {
+    type Item = Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Result<Vec<u8>>> {
+        let mut buf = Vec::new();
+        match self.buf.read_until_seq(self.delim, &mut buf) {
+            Ok(0) => None,
+            Ok(_n) => {
+                if buf.ends_with(self.delim) {
+                    buf.truncate(buf.len() - self.delim.len());
+                }
+                Some(Ok(buf))
+            }
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
 /// An iterator over the lines of an instance of `BufRead`.
 ///
 /// This struct is generally created by calling [`lines`] on a `BufRead`.
@@ -474,15 +1182,82 @@ impl<B: BufRead> Iterator for Split<B> {
 #[derive(Debug)]
 pub struct Lines<B> {
     buf: B,
+    max_len: Option<usize>,
+    // Set when the previous line was too long, to discard the rest of it.
+    skip_rest: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<B> Lines<B> {
+    /// Limits the length of each line, including its terminator, to `max`
+    /// bytes.
+    ///
+    /// A longer line yields [`Error::OutOfRange`] instead of being read into
+    /// memory. The rest of that line is discarded, and iteration resumes with
+    /// the line after it.
+    pub fn with_max_len(mut self, max: usize) -> Self {
+        self.max_len = Some(max);
+        self
+    }
 }
 
 #[cfg(feature = "alloc")]
 impl<B: BufRead> Iterator for Lines<B> {
     type Item = Result<String>;
 
+    fn next(&mut self) -> Option<Result<String>> {
+        if self.skip_rest {
+            if let Err(e) = self.buf.skip_until(b'\n') {
+                return Some(Err(e));
+            }
+            self.skip_rest = false;
+        }
+        let mut buf = String::new();
+        let res = match self.max_len {
+            Some(max) => self.buf.read_line_limited(&mut buf, max),
+            None => self.buf.read_line(&mut buf),
+        };
+        match res {
+            Ok(0) => None,
+            Ok(_n) => {
+                if buf.ends_with('\n') {
+                    buf.pop();
+                    if buf.ends_with('\r') {
+                        buf.pop();
+                    }
+                }
+                Some(Ok(buf))
+            }
+            Err(e) => {
+                if e == Error::OutOfRange {
+                    self.skip_rest = true;
+                }
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+/// An iterator over the lines of an instance of `BufRead`, with invalid UTF-8
+/// replaced.
+///
+/// This struct is generally created by calling [`lines_lossy`] on a `BufRead`.
+/// Please see the documentation of [`lines_lossy`] for more details.
+///
+/// [`lines_lossy`]: BufRead::lines_lossy
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub struct LinesLossy<B> {
+    buf: B,
+}
+
+#[cfg(feature = "alloc")]
+impl<B: BufRead> Iterator for LinesLossy<B> {
+    type Item = Result<String>;
+
     fn next(&mut self) -> Option<Result<String>> {
         let mut buf = String::new();
-        match self.buf.read_line(&mut buf) {
+        match self.buf.read_line_lossy(&mut buf) {
             Ok(0) => None,
             Ok(_n) => {
                 if buf.ends_with('\n') {
@@ -0,0 +1,221 @@
+use core::ops::RangeInclusive;
+
+use crate::{BufRead, Error, Read, Result};
+
+/// Returns the length of the UTF-8 sequence started by `first`, and the range
+/// its second byte must be in, or `None` if `first` cannot start a multi-byte
+/// sequence.
+fn utf8_lead(first: u8) -> Option<(usize, RangeInclusive<u8>)> {
+    Some(match first {
+        0xC2..=0xDF => (2, 0x80..=0xBF),
+        0xE0 => (3, 0xA0..=0xBF),
+        0xE1..=0xEC | 0xEE..=0xEF => (3, 0x80..=0xBF),
+        0xED => (3, 0x80..=0x9F),
+        0xF0 => (4, 0x90..=0xBF),
+        0xF1..=0xF3 => (4, 0x80..=0xBF),
+        0xF4 => (4, 0x80..=0x8F),
+        _ => return None,
+    })
+}
+
+fn peek_byte<R: BufRead + ?Sized>(r: &mut R) -> Result<Option<u8>> {
+    Ok(r.fill_buf()?.first().copied())
+}
+
+/// Reads the UTF-8 encoding of one character into `out`, across refills if
+/// needed, and returns its length. Returns `None` at EOF.
+///
+/// On an invalid or truncated sequence, the bytes that belong to it are
+/// consumed and [`Error::IllegalBytes`] is returned. The first byte that does
+/// not fit is left in the reader, so decoding can go on from there.
+pub(crate) fn read_char<R: BufRead + ?Sized>(
+    r: &mut R,
+    out: &mut [u8; 4],
+) -> Result<Option<usize>> {
+    let Some(first) = peek_byte(r)? else {
+        return Ok(None);
+    };
+    r.consume(1);
+    out[0] = first;
+    if first.is_ascii() {
+        return Ok(Some(1));
+    }
+    let Some((width, second)) = utf8_lead(first) else {
+        return Err(Error::IllegalBytes);
+    };
+    for (i, slot) in out.iter_mut().enumerate().take(width).skip(1) {
+        let valid = if i == 1 { second.clone() } else { 0x80..=0xBF };
+        match peek_byte(r)? {
+            Some(b) if valid.contains(&b) => {
+                r.consume(1);
+                *slot = b;
+            }
+            _ => return Err(Error::IllegalBytes),
+        }
+    }
+    Ok(Some(width))
+}
+
+/// An iterator over the `char`s of a reader.
+///
+/// This struct is generally created by calling [`chars`] on a `BufRead`.
+/// Please see the documentation of [`chars`] for more details.
+///
+/// [`chars`]: BufRead::chars
+#[derive(Debug)]
+pub struct Chars<R> {
+    pub(crate) inner: R,
+}
+
+impl<R: BufRead> Iterator for Chars<R> {
+    type Item = Result<char>;
+
+    fn next(&mut self) -> Option<Result<char>> {
+        let mut bytes = [0; 4];
+        match read_char(&mut self.inner, &mut bytes) {
+            Ok(None) => None,
+            Ok(Some(n)) => Some(
+                str::from_utf8(&bytes[..n])
+                    .ok()
+                    .and_then(|s| s.chars().next())
+                    .ok_or(Error::IllegalBytes),
+            ),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// A [`BufRead`] adapter that only lets valid UTF-8 through.
+///
+/// Everything returned by [`fill_buf`] ends on a character boundary, so each
+/// buffer can be converted with [`str::from_utf8`] on its own. A character
+/// that straddles a refill of the inner reader is assembled in a small
+/// internal buffer.
+///
+/// When an invalid or truncated sequence is found, the data before it is
+/// still returned, then reading fails with [`Error::IllegalBytes`] and
+/// [`error_offset`] reports where the sequence starts. Every later read fails
+/// the same way.
+///
+/// [`fill_buf`]: BufRead::fill_buf
+/// [`error_offset`]: Utf8Reader::error_offset
+#[derive(Debug)]
+pub struct Utf8Reader<R> {
+    inner: R,
+    // Length of the validated prefix of the inner buffer.
+    valid: usize,
+    // A character assembled across refills, `carry[pos..len]` is unread.
+    carry: [u8; 4],
+    carry_pos: usize,
+    carry_len: usize,
+    offset: u64,
+    error_offset: Option<u64>,
+}
+
+impl<R> Utf8Reader<R> {
+    /// Creates a new `Utf8Reader<R>` reading from `inner`.
+    pub const fn new(inner: R) -> Utf8Reader<R> {
+        Utf8Reader {
+            inner,
+            valid: 0,
+            carry: [0; 4],
+            carry_pos: 0,
+            carry_len: 0,
+            offset: 0,
+            error_offset: None,
+        }
+    }
+
+    /// Returns the number of valid bytes consumed so far.
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Returns the offset of the first invalid sequence, if one was found.
+    pub const fn error_offset(&self) -> Option<u64> {
+        self.error_offset
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Utf8Reader<R>`, returning the underlying reader.
+    ///
+    /// A partially read character that straddled a refill is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: BufRead> Read for Utf8Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let nread = {
+            let mut rem = self.fill_buf()?;
+            rem.read(buf)?
+        };
+        self.consume(nread);
+        Ok(nread)
+    }
+}
+
+impl<R: BufRead> BufRead for Utf8Reader<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.error_offset.is_some() {
+            return Err(Error::IllegalBytes);
+        }
+        if self.carry_pos < self.carry_len {
+            return Ok(&self.carry[self.carry_pos..self.carry_len]);
+        }
+        if self.valid == 0 {
+            let available = self.inner.fill_buf()?;
+            let (valid, invalid) = match str::from_utf8(available) {
+                Ok(_) => (available.len(), false),
+                Err(e) => (e.valid_up_to(), e.error_len().is_some()),
+            };
+            if valid == 0 && !available.is_empty() {
+                if invalid {
+                    self.error_offset = Some(self.offset);
+                    return Err(Error::IllegalBytes);
+                }
+                // A character split by the end of the buffer.
+                match read_char(&mut self.inner, &mut self.carry) {
+                    Ok(n) => {
+                        self.carry_pos = 0;
+                        self.carry_len = n.unwrap_or(0);
+                        return Ok(&self.carry[..self.carry_len]);
+                    }
+                    Err(e) => {
+                        if e == Error::IllegalBytes {
+                            self.error_offset = Some(self.offset);
+                        }
+                        return Err(e);
+                    }
+                }
+            }
+            self.valid = valid;
+        }
+        let available = self.inner.fill_buf()?;
+        Ok(&available[..core::cmp::min(self.valid, available.len())])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        let amt = if self.carry_pos < self.carry_len {
+            let amt = core::cmp::min(amt, self.carry_len - self.carry_pos);
+            self.carry_pos += amt;
+            amt
+        } else {
+            let amt = core::cmp::min(amt, self.valid);
+            self.inner.consume(amt);
+            self.valid -= amt;
+            amt
+        };
+        self.offset += amt as u64;
+    }
+}
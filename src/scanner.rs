@@ -0,0 +1,271 @@
+use core::str::FromStr;
+
+use crate::{BufRead, Error, Result};
+
+/// Default maximum length of a token that spans a refill of the buffer.
+const DEFAULT_TOKEN_LEN: usize = 64;
+
+fn is_delim(b: u8, comment: Option<u8>) -> bool {
+    b.is_ascii_whitespace() || Some(b) == comment
+}
+
+/// A tokenizer that reads whitespace-separated words and numbers from a
+/// [`BufRead`].
+///
+/// A token that lies entirely within the reader's buffer is borrowed from it.
+/// One that spans a refill is copied into an internal buffer of `N` bytes;
+/// a longer one is skipped and reported with [`Error::OutOfRange`]. Nothing
+/// is allocated.
+///
+/// The scanner keeps track of the line and column it is at. When parsing a
+/// token fails, the position at which it starts is available from
+/// [`error_position`].
+///
+/// [`error_position`]: Scanner::error_position
+#[derive(Debug)]
+pub struct Scanner<R, const N: usize = DEFAULT_TOKEN_LEN> {
+    inner: R,
+    scratch: [u8; N],
+    // Length of the last token, if it was borrowed from the inner buffer and
+    // has not been consumed yet.
+    pending: usize,
+    line: u64,
+    column: u64,
+    token_start: (u64, u64),
+    error_position: Option<(u64, u64)>,
+    comment: Option<u8>,
+}
+
+impl<R> Scanner<R> {
+    /// Creates a new `Scanner<R>` that can copy tokens of up to 64 bytes.
+    pub const fn new(inner: R) -> Scanner<R> {
+        Self::with_token_len(inner)
+    }
+}
+
+impl<R, const N: usize> Scanner<R, N> {
+    /// Creates a new `Scanner<R, N>` that can copy tokens of up to `N` bytes.
+    pub const fn with_token_len(inner: R) -> Scanner<R, N> {
+        Scanner {
+            inner,
+            scratch: [0; N],
+            pending: 0,
+            line: 1,
+            column: 1,
+            token_start: (1, 1),
+            error_position: None,
+            comment: None,
+        }
+    }
+
+    /// Makes the scanner skip everything from `marker` to the end of the line,
+    /// such as `#` comments in a shell script.
+    ///
+    /// The marker also ends a token that it directly follows.
+    pub fn with_comment(mut self, marker: u8) -> Self {
+        self.comment = Some(marker);
+        self
+    }
+
+    /// Returns the line and column of the next byte to be scanned, both
+    /// starting at 1.
+    pub const fn position(&self) -> (u64, u64) {
+        (self.line, self.column + self.pending as u64)
+    }
+
+    /// Returns the line and column at which the token that most recently
+    /// failed to parse starts.
+    pub const fn error_position(&self) -> Option<(u64, u64)> {
+        self.error_position
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// If the last token was borrowed from the reader's buffer, it has not
+    /// been consumed yet. Bytes read directly from the underlying reader are
+    /// not accounted for in the position.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Scanner<R, N>`, returning the underlying reader.
+    ///
+    /// If the last token was borrowed from the reader's buffer, it has not
+    /// been consumed yet.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn fail<T>(&mut self, error: Error) -> Result<T> {
+        self.error_position = Some(self.token_start);
+        Err(error)
+    }
+}
+
+impl<R: BufRead, const N: usize> Scanner<R, N> {
+    fn consume_pending(&mut self) {
+        if self.pending > 0 {
+            self.inner.consume(self.pending);
+            self.column += self.pending as u64;
+            self.pending = 0;
+        }
+    }
+
+    /// Skips whitespace and comments. Returns `false` at EOF.
+    fn skip_space(&mut self) -> Result<bool> {
+        let mut in_comment = false;
+        loop {
+            let (used, found) = {
+                let available = self.inner.fill_buf()?;
+                if available.is_empty() {
+                    return Ok(false);
+                }
+                let mut used = available.len();
+                for (i, &b) in available.iter().enumerate() {
+                    if b == b'\n' {
+                        self.line += 1;
+                        self.column = 1;
+                        in_comment = false;
+                        continue;
+                    }
+                    if !in_comment && !b.is_ascii_whitespace() {
+                        if Some(b) != self.comment {
+                            used = i;
+                            break;
+                        }
+                        in_comment = true;
+                    }
+                    self.column += 1;
+                }
+                (used, used < available.len())
+            };
+            self.inner.consume(used);
+            if found {
+                self.token_start = (self.line, self.column);
+                return Ok(true);
+            }
+        }
+    }
+
+    /// Consumes the rest of the current token, calling `f` on each of its
+    /// bytes. Returns the number of bytes consumed.
+    fn scan_token(&mut self, mut f: impl FnMut(u8)) -> Result<usize> {
+        let mut len = 0;
+        loop {
+            let (used, done) = {
+                let available = self.inner.fill_buf()?;
+                let used = available
+                    .iter()
+                    .position(|&b| is_delim(b, self.comment))
+                    .unwrap_or(available.len());
+                available[..used].iter().for_each(|&b| f(b));
+                (used, used < available.len() || available.is_empty())
+            };
+            self.inner.consume(used);
+            self.column += used as u64;
+            len += used;
+            if done {
+                return Ok(len);
+            }
+        }
+    }
+
+    /// Returns the next whitespace-separated token, or `None` at EOF.
+    ///
+    /// A token longer than `N` bytes that spans a refill of the reader's
+    /// buffer is skipped, and [`Error::OutOfRange`] is returned.
+    pub fn next_token(&mut self) -> Result<Option<&[u8]>> {
+        self.consume_pending();
+        if !self.skip_space()? {
+            return Ok(None);
+        }
+        let end = {
+            let available = self.inner.fill_buf()?;
+            available.iter().position(|&b| is_delim(b, self.comment))
+        };
+        if let Some(end) = end {
+            let available = self.inner.fill_buf()?;
+            #[cfg(feature = "no-panic")]
+            if end > available.len() {
+                return Err(Error::InvalidData);
+            }
+            self.pending = end;
+            return Ok(Some(&available[..end]));
+        }
+
+        // The token continues past the buffer, so it has to be copied.
+        let mut scratch = [0; N];
+        let mut len = 0;
+        self.scan_token(|b| {
+            if let Some(slot) = scratch.get_mut(len) {
+                *slot = b;
+            }
+            len += 1;
+        })?;
+        if len > N {
+            return self.fail(Error::OutOfRange);
+        }
+        self.scratch = scratch;
+        Ok(Some(&self.scratch[..len]))
+    }
+
+    /// Parses the next token as a `T`, or returns `None` at EOF.
+    ///
+    /// Returns [`Error::InvalidData`] if the token is not valid UTF-8 or does
+    /// not parse.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<T: FromStr>(&mut self) -> Result<Option<T>> {
+        let parsed = match self.next_token()? {
+            Some(token) => str::from_utf8(token).ok().and_then(|s| s.parse().ok()),
+            None => return Ok(None),
+        };
+        match parsed {
+            Some(value) => Ok(Some(value)),
+            None => self.fail(Error::InvalidData),
+        }
+    }
+
+    /// Parses the next token as an unsigned integer in the given `radix`
+    /// straight from the reader's buffer, or returns `None` at EOF.
+    ///
+    /// Returns [`Error::InvalidData`] if the token contains a byte that is
+    /// not a digit, [`Error::OutOfRange`] if the value does not fit in a
+    /// `u64`, and [`Error::InvalidInput`] if `radix` is not in `2..=36`. The
+    /// whole token is consumed in any case.
+    pub fn next_u64_radix(&mut self, radix: u32) -> Result<Option<u64>> {
+        if !(2..=36).contains(&radix) {
+            return Err(Error::InvalidInput);
+        }
+        self.consume_pending();
+        if !self.skip_space()? {
+            return Ok(None);
+        }
+        let mut value: Option<u64> = Some(0);
+        let mut error = None;
+        self.scan_token(|b| {
+            if error.is_some() {
+                return;
+            }
+            match char::from(b).to_digit(radix) {
+                Some(d) => {
+                    value = value
+                        .and_then(|v| v.checked_mul(u64::from(radix)))
+                        .and_then(|v| v.checked_add(u64::from(d)));
+                    if value.is_none() {
+                        error = Some(Error::OutOfRange);
+                    }
+                }
+                None => error = Some(Error::InvalidData),
+            }
+        })?;
+        match (error, value) {
+            (None, Some(value)) => Ok(Some(value)),
+            (error, _) => self.fail(error.unwrap_or(Error::OutOfRange)),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use crate::Result;
 
 mod impls;
+mod window;
+
+pub use self::window::Window;
 
 /// Enumeration of possible methods to seek within an I/O object.
 ///
@@ -25,6 +28,26 @@ pub enum SeekFrom {
     Current(i64),
 }
 
+/// Resolves `style` to an absolute position in a stream of `len` bytes whose
+/// current position is `pos`.
+///
+/// Returns [`InvalidInput`] if the position would be negative or overflow.
+///
+/// [`InvalidInput`]: crate::Error::InvalidInput
+pub(crate) fn resolve_seek(style: SeekFrom, pos: u64, len: u64) -> Result<u64> {
+    let (base_pos, offset) = match style {
+        SeekFrom::Start(n) => return Ok(n),
+        SeekFrom::End(n) => (len, n),
+        SeekFrom::Current(n) => (pos, n),
+    };
+    base_pos.checked_add_signed(offset).ok_or_else(|| {
+        axerrno::ax_err_type!(
+            InvalidInput,
+            "invalid seek to a negative or overflowing position"
+        )
+    })
+}
+
 /// Default [`Seek::stream_len`] implementation.
 pub fn default_stream_len<T: Seek + ?Sized>(this: &mut T) -> Result<u64> {
     let old_pos = this.stream_position()?;
@@ -79,4 +102,15 @@ pub trait Seek {
         self.seek(SeekFrom::Current(offset))?;
         Ok(())
     }
+
+    /// Creates an adapter that exposes the range `[start, start + len)` of
+    /// this stream as a stream of its own.
+    ///
+    /// See [`Window`] for more details.
+    fn window(self, start: u64, len: u64) -> Window<Self>
+    where
+        Self: Sized,
+    {
+        Window::new(self, start, len)
+    }
 }
@@ -0,0 +1,123 @@
+use core::cmp;
+
+use crate::{Read, Result, Seek, SeekFrom};
+
+/// A view of the range `[start, start + len)` of a seekable stream, as a
+/// stream of its own.
+///
+/// Positions are relative to `start`, [`stream_len`] returns `len`, and reads
+/// stop at the end of the window. Seeking follows the rules documented on
+/// [`SeekFrom`]: seeking past the end is allowed, and reads there return 0
+/// bytes, while seeking before the start is an error.
+///
+/// The inner stream is only seeked when a read finds it somewhere else than
+/// where the window expects it.
+///
+/// This struct is generally created by calling [`window`] on a `Seek`.
+///
+/// [`stream_len`]: Seek::stream_len
+/// [`window`]: Seek::window
+#[derive(Debug)]
+pub struct Window<R> {
+    inner: R,
+    start: u64,
+    len: u64,
+    pos: u64,
+    // Absolute position of the inner stream, if known.
+    inner_pos: Option<u64>,
+}
+
+impl<R> Window<R> {
+    /// Creates a new `Window<R>` over `[start, start + len)` of `inner`.
+    pub const fn new(inner: R, start: u64, len: u64) -> Window<R> {
+        Window {
+            inner,
+            start,
+            len,
+            pos: 0,
+            inner_pos: None,
+        }
+    }
+
+    /// Returns the offset of the window in the inner stream.
+    pub const fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Returns the length of the window.
+    pub const fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the window is empty.
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets a reference to the underlying stream.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying stream.
+    ///
+    /// The underlying stream may be seeked freely, the window seeks it back
+    /// before the next read.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner_pos = None;
+        &mut self.inner
+    }
+
+    /// Unwraps this `Window<R>`, returning the underlying stream.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read + Seek> Read for Window<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let remaining = self.len.saturating_sub(self.pos);
+        let max = cmp::min(buf.len() as u64, remaining) as usize;
+        if max == 0 {
+            return Ok(0);
+        }
+        let target = self.start.saturating_add(self.pos);
+        if self.inner_pos != Some(target) {
+            self.inner_pos = None;
+            self.inner.seek(SeekFrom::Start(target))?;
+        }
+        match self.inner.read(&mut buf[..max]) {
+            Ok(n) => {
+                let n = cmp::min(n, max);
+                self.pos += n as u64;
+                self.inner_pos = Some(target + n as u64);
+                Ok(n)
+            }
+            Err(e) => {
+                self.inner_pos = None;
+                Err(e)
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len.saturating_sub(self.pos);
+        let upper = usize::try_from(remaining).ok();
+        (0, upper)
+    }
+}
+
+impl<R: Seek> Seek for Window<R> {
+    fn seek(&mut self, style: SeekFrom) -> Result<u64> {
+        self.pos = crate::seek::resolve_seek(style, self.pos, self.len)?;
+        Ok(self.pos)
+    }
+
+    fn stream_len(&mut self) -> Result<u64> {
+        Ok(self.len)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
@@ -0,0 +1,185 @@
+use core::io::BorrowedCursor;
+
+use crate::{BufRead, Error, Read, Result, Seek, SeekFrom};
+
+/// A reader adapter that counts the bytes read through it, and optionally the
+/// lines and columns.
+///
+/// Bytes are counted when they are returned by [`read`] or [`read_buf`], or
+/// marked as read with [`consume`], so the [`BufRead`] helpers built on top of
+/// those are accounted for as well.
+///
+/// `Tracked` implements [`Seek`] for `SeekFrom::Current(0)` only, which makes
+/// [`stream_position`] work on sources that cannot seek.
+///
+/// [`read`]: Read::read
+/// [`read_buf`]: Read::read_buf
+/// [`consume`]: BufRead::consume
+/// [`stream_position`]: Seek::stream_position
+#[derive(Debug)]
+pub struct Tracked<R> {
+    inner: R,
+    position: u64,
+    track_lines: bool,
+    line: u64,
+    column: u64,
+}
+
+impl<R> Tracked<R> {
+    /// Creates a new `Tracked<R>` that counts bytes only.
+    pub const fn new(inner: R) -> Tracked<R> {
+        Tracked {
+            inner,
+            position: 0,
+            track_lines: false,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Creates a new `Tracked<R>` that also tracks lines and columns.
+    ///
+    /// To count the lines in consumed data, [`consume`] looks at the inner
+    /// reader's buffer again with [`fill_buf`], which is not expected to read
+    /// from the source while the buffer is not empty.
+    ///
+    /// [`consume`]: BufRead::consume
+    /// [`fill_buf`]: BufRead::fill_buf
+    pub const fn with_lines(inner: R) -> Tracked<R> {
+        Tracked {
+            inner,
+            position: 0,
+            track_lines: true,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Returns the number of bytes read so far.
+    pub const fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Returns the line of the next byte to be read, starting at 1.
+    ///
+    /// This is always 1 unless the reader was created with [`with_lines`].
+    ///
+    /// [`with_lines`]: Tracked::with_lines
+    pub const fn line(&self) -> u64 {
+        self.line
+    }
+
+    /// Returns the column of the next byte to be read, in bytes, starting at
+    /// 1.
+    ///
+    /// This is always 1 unless the reader was created with [`with_lines`].
+    ///
+    /// [`with_lines`]: Tracked::with_lines
+    pub const fn column(&self) -> u64 {
+        self.column
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader are not accounted for.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Tracked<R>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn advance(&mut self, n: usize) {
+        self.position += n as u64;
+    }
+
+    fn advance_lines(position: &mut u64, line: &mut u64, column: &mut u64, data: &[u8]) {
+        *position += data.len() as u64;
+        match memchr::memrchr(b'\n', data) {
+            Some(last) => {
+                *line += memchr::memchr_iter(b'\n', data).count() as u64;
+                *column = (data.len() - last) as u64;
+            }
+            None => *column += data.len() as u64,
+        }
+    }
+}
+
+impl<R: Read> Read for Tracked<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        if self.track_lines {
+            let data = &buf[..n.min(buf.len())];
+            Self::advance_lines(&mut self.position, &mut self.line, &mut self.column, data);
+        } else {
+            self.advance(n);
+        }
+        Ok(n)
+    }
+
+    fn read_buf(&mut self, mut cursor: BorrowedCursor<'_>) -> Result<()> {
+        if self.track_lines {
+            // The cursor does not give access to what was written, so go
+            // through `read` to see the data.
+            return crate::default_read_buf(|b| self.read(b), cursor);
+        }
+        let prev = cursor.written();
+        let res = self.inner.read_buf(cursor.reborrow());
+        self.advance(cursor.written() - prev);
+        res
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<R: BufRead> BufRead for Tracked<R> {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if self.track_lines && amt > 0 {
+            match self.inner.fill_buf() {
+                Ok(buf) => {
+                    let data = &buf[..amt.min(buf.len())];
+                    Self::advance_lines(&mut self.position, &mut self.line, &mut self.column, data);
+                }
+                Err(_) => self.advance(amt),
+            }
+        } else {
+            self.advance(amt);
+        }
+        self.inner.consume(amt);
+    }
+
+    // Filling does not move the position: it is counted by `consume` like for
+    // `fill_buf`.
+    fn fill_buf_at_least(&mut self, n: usize) -> Result<&[u8]> {
+        self.inner.fill_buf_at_least(n)
+    }
+}
+
+impl<R> Seek for Tracked<R> {
+    /// Returns the current position for `SeekFrom::Current(0)`.
+    ///
+    /// Any other seek fails with [`Error::Unsupported`].
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        match pos {
+            SeekFrom::Current(0) => Ok(self.position),
+            _ => Err(Error::Unsupported),
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.position)
+    }
+}
@@ -0,0 +1,270 @@
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+
+use crate::{Error, Read, Result, Write};
+
+/// Byte order of UTF-16 code units.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Utf16Endian {
+    /// Little endian, as used by UEFI and Windows.
+    #[default]
+    Little,
+    /// Big endian.
+    Big,
+}
+
+impl Utf16Endian {
+    fn decode(self, bytes: [u8; 2]) -> u16 {
+        match self {
+            Utf16Endian::Little => u16::from_le_bytes(bytes),
+            Utf16Endian::Big => u16::from_be_bytes(bytes),
+        }
+    }
+
+    fn encode(self, unit: u16) -> [u8; 2] {
+        match self {
+            Utf16Endian::Little => unit.to_le_bytes(),
+            Utf16Endian::Big => unit.to_be_bytes(),
+        }
+    }
+}
+
+/// A decoder for UTF-16 text read from a byte stream.
+///
+/// `Utf16Reader` is an iterator over the decoded `char`s. Code units are read
+/// two bytes at a time, so wrapping an unbuffered reader in a
+/// [`BufReader`] is recommended. Surrogate pairs may straddle reads of the
+/// inner reader.
+///
+/// An unpaired surrogate or a trailing odd byte yields [`Error::InvalidData`].
+/// Decoding goes on after an unpaired surrogate, starting with the code unit
+/// that did not fit.
+///
+/// [`BufReader`]: crate::BufReader
+#[derive(Debug)]
+pub struct Utf16Reader<R> {
+    inner: R,
+    endian: Utf16Endian,
+    detect_bom: bool,
+    // A code unit read while looking for a low surrogate, to decode next.
+    pending: Option<u16>,
+    offset: u64,
+}
+
+impl<R> Utf16Reader<R> {
+    /// Creates a new `Utf16Reader<R>` that detects the byte order from a
+    /// leading byte order mark.
+    ///
+    /// The byte order mark is skipped. Without one, the text is decoded as
+    /// little endian.
+    pub const fn new(inner: R) -> Utf16Reader<R> {
+        Utf16Reader {
+            inner,
+            endian: Utf16Endian::Little,
+            detect_bom: true,
+            pending: None,
+            offset: 0,
+        }
+    }
+
+    /// Creates a new `Utf16Reader<R>` that decodes with the given byte order.
+    ///
+    /// A leading byte order mark is not treated specially, and decodes as
+    /// U+FEFF.
+    pub const fn with_endian(inner: R, endian: Utf16Endian) -> Utf16Reader<R> {
+        Utf16Reader {
+            inner,
+            endian,
+            detect_bom: false,
+            pending: None,
+            offset: 0,
+        }
+    }
+
+    /// Returns the byte order in use.
+    ///
+    /// If it is detected from a byte order mark, it is only known once the
+    /// first character has been read.
+    pub const fn endian(&self) -> Utf16Endian {
+        self.endian
+    }
+
+    /// Returns the number of bytes read from the inner reader so far.
+    pub const fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub const fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read directly from the underlying reader are not accounted for.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Utf16Reader<R>`, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Utf16Reader<R> {
+    fn read_unit(&mut self) -> Result<Option<u16>> {
+        if let Some(unit) = self.pending.take() {
+            return Ok(Some(unit));
+        }
+        let mut bytes = [0; 2];
+        match self.inner.read_exact_counted(&mut bytes) {
+            Ok(()) => {}
+            Err(e) => {
+                self.offset += e.done as u64;
+                return match (e.done, e.error) {
+                    (0, Error::UnexpectedEof) => Ok(None),
+                    (_, Error::UnexpectedEof) => Err(Error::InvalidData),
+                    (_, error) => Err(error),
+                };
+            }
+        }
+        self.offset += 2;
+        if self.detect_bom {
+            self.detect_bom = false;
+            match bytes {
+                [0xFF, 0xFE] => self.endian = Utf16Endian::Little,
+                [0xFE, 0xFF] => self.endian = Utf16Endian::Big,
+                _ => return Ok(Some(self.endian.decode(bytes))),
+            }
+            return self.read_unit();
+        }
+        Ok(Some(self.endian.decode(bytes)))
+    }
+
+    /// Reads the next character, or returns `None` at EOF.
+    pub fn read_char(&mut self) -> Result<Option<char>> {
+        let Some(unit) = self.read_unit()? else {
+            return Ok(None);
+        };
+        match unit {
+            0xD800..=0xDBFF => match self.read_unit()? {
+                Some(low @ 0xDC00..=0xDFFF) => {
+                    let c =
+                        0x10000 + ((u32::from(unit) - 0xD800) << 10) + (u32::from(low) - 0xDC00);
+                    char::from_u32(c).map(Some).ok_or(Error::InvalidData)
+                }
+                Some(other) => {
+                    self.pending = Some(other);
+                    Err(Error::InvalidData)
+                }
+                None => Err(Error::InvalidData),
+            },
+            0xDC00..=0xDFFF => Err(Error::InvalidData),
+            _ => char::from_u32(u32::from(unit))
+                .map(Some)
+                .ok_or(Error::InvalidData),
+        }
+    }
+
+    /// Reads all characters until EOF and appends them to `buf`, returning
+    /// the number of bytes appended.
+    ///
+    /// If an error occurs, the characters decoded before it are kept in
+    /// `buf`.
+    #[cfg(feature = "alloc")]
+    pub fn read_to_string(&mut self, buf: &mut String) -> Result<usize> {
+        let start = buf.len();
+        while let Some(c) = self.read_char()? {
+            let mut bytes = [0; 4];
+            let c = c.encode_utf8(&mut bytes);
+            buf.try_reserve(c.len()).map_err(|_| Error::NoMemory)?;
+            #[cfg(feature = "no-panic")]
+            if buf.capacity() - buf.len() < c.len() {
+                return Err(Error::NoMemory);
+            }
+            buf.push_str(c);
+        }
+        Ok(buf.len() - start)
+    }
+}
+
+impl<R: Read> Iterator for Utf16Reader<R> {
+    type Item = Result<char>;
+
+    fn next(&mut self) -> Option<Result<char>> {
+        self.read_char().transpose()
+    }
+}
+
+/// An encoder that writes text to a byte stream as UTF-16.
+#[derive(Debug)]
+pub struct Utf16Writer<W> {
+    inner: W,
+    endian: Utf16Endian,
+}
+
+impl<W> Utf16Writer<W> {
+    /// Creates a new `Utf16Writer<W>` that encodes with the given byte order.
+    pub const fn new(inner: W, endian: Utf16Endian) -> Utf16Writer<W> {
+        Utf16Writer { inner, endian }
+    }
+
+    /// Returns the byte order in use.
+    pub const fn endian(&self) -> Utf16Endian {
+        self.endian
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub const fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwraps this `Utf16Writer<W>`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Utf16Writer<W> {
+    /// Writes a byte order mark.
+    pub fn write_bom(&mut self) -> Result<()> {
+        self.write_char('\u{FEFF}')
+    }
+
+    /// Encodes and writes a single character.
+    pub fn write_char(&mut self, c: char) -> Result<()> {
+        let mut units = [0; 2];
+        let mut bytes = [0; 4];
+        let units = c.encode_utf16(&mut units);
+        for (unit, out) in units.iter().zip(bytes.chunks_exact_mut(2)) {
+            out.copy_from_slice(&self.endian.encode(*unit));
+        }
+        self.inner.write_all(&bytes[..units.len() * 2])
+    }
+
+    /// Encodes and writes a string.
+    pub fn write_str(&mut self, s: &str) -> Result<()> {
+        let mut bytes = [0; 256];
+        let mut len = 0;
+        for unit in s.encode_utf16() {
+            if len + 2 > bytes.len() {
+                self.inner.write_all(&bytes)?;
+                len = 0;
+            }
+            bytes[len..len + 2].copy_from_slice(&self.endian.encode(unit));
+            len += 2;
+        }
+        self.inner.write_all(&bytes[..len])
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
@@ -2,7 +2,7 @@
 use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
 use core::{cmp, fmt, io::BorrowedCursor, mem};
 
-use crate::{Error, Result, Write};
+use crate::{BufWrite, Error, PartialTransfer, Result, Write};
 
 // =============================================================================
 // Forwarding implementations
@@ -23,12 +23,29 @@ impl<W: Write + ?Sized> Write for &mut W {
         (**self).write_all(buf)
     }
 
+    #[inline]
+    fn write_all_counted(&mut self, buf: &[u8]) -> core::result::Result<(), PartialTransfer> {
+        (**self).write_all_counted(buf)
+    }
+
     #[inline]
     fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
         (**self).write_fmt(fmt)
     }
 }
 
+impl<W: BufWrite + ?Sized> BufWrite for &mut W {
+    #[inline]
+    fn reserve(&mut self, min: usize) -> Result<&mut [u8]> {
+        (**self).reserve(min)
+    }
+
+    #[inline]
+    fn commit(&mut self, n: usize) {
+        (**self).commit(n)
+    }
+}
+
 #[cfg(feature = "alloc")]
 impl<W: Write + ?Sized> Write for Box<W> {
     #[inline]
@@ -46,12 +63,30 @@ impl<W: Write + ?Sized> Write for Box<W> {
         (**self).write_all(buf)
     }
 
+    #[inline]
+    fn write_all_counted(&mut self, buf: &[u8]) -> core::result::Result<(), PartialTransfer> {
+        (**self).write_all_counted(buf)
+    }
+
     #[inline]
     fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> Result<()> {
         (**self).write_fmt(fmt)
     }
 }
 
+#[cfg(feature = "alloc")]
+impl<W: BufWrite + ?Sized> BufWrite for Box<W> {
+    #[inline]
+    fn reserve(&mut self, min: usize) -> Result<&mut [u8]> {
+        (**self).reserve(min)
+    }
+
+    #[inline]
+    fn commit(&mut self, n: usize) {
+        (**self).commit(n)
+    }
+}
+
 // =============================================================================
 // In-memory buffer implementations
 
@@ -78,12 +113,39 @@ impl Write for &mut [u8] {
             Ok(())
         }
     }
+
+    #[inline]
+    fn write_all_counted(&mut self, data: &[u8]) -> core::result::Result<(), PartialTransfer> {
+        let done = self.write(data).map_err(|e| PartialTransfer::new(0, e))?;
+        if done < data.len() {
+            Err(PartialTransfer::new(done, Error::WriteZero))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl BufWrite for &mut [u8] {
+    #[inline]
+    fn reserve(&mut self, min: usize) -> Result<&mut [u8]> {
+        if self.len() < min {
+            return Err(Error::WriteZero);
+        }
+        Ok(&mut **self)
+    }
+
+    #[inline]
+    fn commit(&mut self, n: usize) {
+        let n = cmp::min(n, self.len());
+        *self = &mut mem::take(self)[n..];
+    }
 }
 
 #[cfg(feature = "alloc")]
 impl Write for Vec<u8> {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.try_reserve(buf.len()).map_err(|_| Error::NoMemory)?;
         self.extend_from_slice(buf);
         Ok(buf.len())
     }
@@ -95,6 +157,7 @@ impl Write for Vec<u8> {
 
     #[inline]
     fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.try_reserve(buf.len()).map_err(|_| Error::NoMemory)?;
         self.extend_from_slice(buf);
         Ok(())
     }
@@ -104,6 +167,7 @@ impl Write for Vec<u8> {
 impl Write for VecDeque<u8> {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.try_reserve(buf.len()).map_err(|_| Error::NoMemory)?;
         self.extend(buf);
         Ok(buf.len())
     }
@@ -115,6 +179,7 @@ impl Write for VecDeque<u8> {
 
     #[inline]
     fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        self.try_reserve(buf.len()).map_err(|_| Error::NoMemory)?;
         self.extend(buf);
         Ok(())
     }
@@ -141,4 +206,14 @@ impl Write for BorrowedCursor<'_> {
             Ok(())
         }
     }
+
+    #[inline]
+    fn write_all_counted(&mut self, buf: &[u8]) -> core::result::Result<(), PartialTransfer> {
+        let done = self.write(buf).map_err(|e| PartialTransfer::new(0, e))?;
+        if done < buf.len() {
+            Err(PartialTransfer::new(done, Error::WriteZero))
+        } else {
+            Ok(())
+        }
+    }
 }
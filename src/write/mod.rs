@@ -1,8 +1,13 @@
-use core::fmt;
+use core::{cmp, fmt};
 
-use crate::{Error, Result};
+use crate::{BufRead, Error, PartialTransfer, Result};
 
 mod impls;
+#[cfg(feature = "alloc")]
+mod vecwriter;
+
+#[cfg(feature = "alloc")]
+pub use self::vecwriter::VecWriter;
 
 pub(crate) fn default_write_fmt<W: Write + ?Sized>(
     this: &mut W,
@@ -40,6 +45,9 @@ pub(crate) fn default_write_fmt<W: Write + ?Sized>(
             } else {
                 // This shouldn't happen: the underlying stream did not error,
                 // but somehow the formatter still errored?
+                #[cfg(feature = "no-panic")]
+                return Err(Error::Io);
+                #[cfg(not(feature = "no-panic"))]
                 panic!(
                     "a formatting trait implementation returned an error when the underlying \
                      stream did not"
@@ -49,6 +57,28 @@ pub(crate) fn default_write_fmt<W: Write + ?Sized>(
     }
 }
 
+/// Default [`Write::write_all_counted`] implementation.
+pub fn default_write_all_counted<W: Write + ?Sized>(
+    this: &mut W,
+    buf: &[u8],
+) -> core::result::Result<(), PartialTransfer> {
+    let mut done = 0;
+    while done < buf.len() {
+        match this.write(&buf[done..]) {
+            Ok(0) => return Err(PartialTransfer::new(done, Error::WriteZero)),
+            #[cfg(feature = "no-panic")]
+            Ok(n) if n > buf.len() - done => {
+                return Err(PartialTransfer::new(done, Error::InvalidData));
+            }
+            Ok(n) => done += n,
+            #[cfg(feature = "continue-on-interrupt")]
+            Err(e) if e.canonicalize() == Error::Interrupted => {}
+            Err(e) => return Err(PartialTransfer::new(done, e)),
+        }
+    }
+    Ok(())
+}
+
 /// A trait for objects which are byte-oriented sinks.
 ///
 /// See [`std::io::Write`] for more details.
@@ -61,17 +91,14 @@ pub trait Write {
     fn flush(&mut self) -> Result<()>;
 
     /// Attempts to write an entire buffer into this writer.
-    fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
-        while !buf.is_empty() {
-            match self.write(buf) {
-                Ok(0) => return Err(Error::WriteZero),
-                Ok(n) => buf = &buf[n..],
-                #[cfg(feature = "continue-on-interrupt")]
-                Err(e) if e.canonicalize() == Error::Interrupted => {}
-                Err(e) => return Err(e),
-            }
-        }
-        Ok(())
+    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
+        default_write_all_counted(self, buf).map_err(Into::into)
+    }
+
+    /// Attempts to write an entire buffer into this writer, reporting how many
+    /// bytes were written if it fails.
+    fn write_all_counted(&mut self, buf: &[u8]) -> core::result::Result<(), PartialTransfer> {
+        default_write_all_counted(self, buf)
     }
 
     /// Writes a formatted string into this writer, returning any error
@@ -93,3 +120,62 @@ pub trait Write {
         self
     }
 }
+
+/// A `BufWrite` is a type of `Write`r which has an internal buffer that can be
+/// written to directly, without going through an intermediate buffer.
+///
+/// It is the write-side counterpart of [`BufRead`]: [`reserve`] borrows a
+/// region of the internal buffer, and [`commit`] marks how much of it was
+/// filled.
+///
+/// A `Vec<u8>` or `VecDeque<u8>` has nowhere to keep track of a reserved
+/// region, so it is wrapped in a [`VecWriter`] to be written to this way.
+///
+/// [`reserve`]: BufWrite::reserve
+/// [`commit`]: BufWrite::commit
+pub trait BufWrite: Write {
+    /// Returns a writable region of at least `min` bytes at the end of the
+    /// internal buffer, making room for it if necessary.
+    ///
+    /// The region may be larger than `min`. Its contents are unspecified.
+    /// Returns [`Error::WriteZero`] if `min` bytes can never be made available.
+    fn reserve(&mut self, min: usize) -> Result<&mut [u8]>;
+
+    /// Marks the first `n` bytes of the region returned by the most recent
+    /// [`reserve`] call as written.
+    ///
+    /// `n` is clamped to the length of that region. `commit` must directly
+    /// follow [`reserve`]: if another method was called on the writer in
+    /// between, which bytes get committed is unspecified.
+    ///
+    /// [`reserve`]: BufWrite::reserve
+    fn commit(&mut self, n: usize);
+}
+
+/// Copies the entire contents of a [`BufRead`] into a [`BufWrite`] without an
+/// intermediate buffer, returning the number of bytes copied.
+///
+/// Data is copied straight from the reader's buffer into the writer's buffer.
+/// The writer is not flushed.
+pub fn copy_buf<R, W>(reader: &mut R, writer: &mut W) -> Result<u64>
+where
+    R: BufRead + ?Sized,
+    W: BufWrite + ?Sized,
+{
+    let mut written = 0;
+    loop {
+        let src = match reader.fill_buf() {
+            Ok([]) => return Ok(written),
+            Ok(src) => src,
+            #[cfg(feature = "continue-on-interrupt")]
+            Err(e) if e.canonicalize() == Error::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        let dst = writer.reserve(1)?;
+        let n = cmp::min(src.len(), dst.len());
+        dst[..n].copy_from_slice(&src[..n]);
+        writer.commit(n);
+        reader.consume(n);
+        written += n as u64;
+    }
+}
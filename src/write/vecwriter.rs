@@ -0,0 +1,223 @@
+use alloc::vec::Vec;
+use core::cmp;
+
+use self::storage::Storage;
+use crate::{BufWrite, DEFAULT_BUF_SIZE, Result, Write};
+
+/// A writer that appends to a `Vec<u8>` or a `VecDeque<u8>`, and implements
+/// [`BufWrite`] on top of it.
+///
+/// [`reserve`] hands out zeroed space past the written data. That space is
+/// zeroed once, when the collection grows, and is reused by later
+/// reservations until it is committed, so writing through [`BufWrite`] does
+/// not zero each region again. It never shows up in the collection returned
+/// by [`get_mut`] or [`into_inner`].
+///
+/// [`reserve`]: BufWrite::reserve
+/// [`get_mut`]: VecWriter::get_mut
+/// [`into_inner`]: VecWriter::into_inner
+#[derive(Debug, Default)]
+pub struct VecWriter<V = Vec<u8>> {
+    inner: V,
+    // Zeroed bytes at the end of `inner` that have not been written yet.
+    spare: usize,
+    // Length of the region returned by the last `reserve`, until `commit`.
+    reserved: usize,
+}
+
+mod storage {
+    use alloc::{collections::VecDeque, vec::Vec};
+
+    use crate::{Error, Result, Write};
+
+    /// The collections a [`VecWriter`] can append to.
+    pub trait Storage: Write {
+        fn len(&self) -> usize;
+
+        fn capacity(&self) -> usize;
+
+        fn try_reserve(&mut self, additional: usize) -> Result<()>;
+
+        fn resize(&mut self, len: usize);
+
+        fn truncate(&mut self, len: usize);
+
+        /// Returns the last `len` bytes as one slice.
+        fn tail_mut(&mut self, len: usize) -> Result<&mut [u8]>;
+    }
+
+    impl Storage for Vec<u8> {
+        fn len(&self) -> usize {
+            self.len()
+        }
+
+        fn capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn try_reserve(&mut self, additional: usize) -> Result<()> {
+            self.try_reserve(additional).map_err(|_| Error::NoMemory)
+        }
+
+        fn resize(&mut self, len: usize) {
+            self.resize(len, 0);
+        }
+
+        fn truncate(&mut self, len: usize) {
+            self.truncate(len);
+        }
+
+        fn tail_mut(&mut self, len: usize) -> Result<&mut [u8]> {
+            let start = self.len().saturating_sub(len);
+            Ok(&mut self[start..])
+        }
+    }
+
+    impl Storage for VecDeque<u8> {
+        fn len(&self) -> usize {
+            self.len()
+        }
+
+        fn capacity(&self) -> usize {
+            self.capacity()
+        }
+
+        fn try_reserve(&mut self, additional: usize) -> Result<()> {
+            self.try_reserve(additional).map_err(|_| Error::NoMemory)
+        }
+
+        fn resize(&mut self, len: usize) {
+            self.resize(len, 0);
+        }
+
+        fn truncate(&mut self, len: usize) {
+            self.truncate(len);
+        }
+
+        fn tail_mut(&mut self, len: usize) -> Result<&mut [u8]> {
+            // Only move the data around if the tail wraps around the buffer.
+            if self.as_slices().1.len() < len && !self.as_slices().1.is_empty() {
+                #[cfg(not(feature = "no-panic"))]
+                self.make_contiguous();
+                #[cfg(feature = "no-panic")]
+                relocate(self)?;
+            }
+            let (front, back) = self.as_mut_slices();
+            let last = if back.is_empty() { front } else { back };
+            let start = last.len().saturating_sub(len);
+            Ok(&mut last[start..])
+        }
+    }
+
+    /// Moves the contents of `deque` to a new allocation, where they do not
+    /// wrap around. Unlike `make_contiguous`, this cannot panic.
+    #[cfg(feature = "no-panic")]
+    fn relocate(deque: &mut VecDeque<u8>) -> Result<()> {
+        let mut fresh = Vec::new();
+        fresh
+            .try_reserve_exact(deque.capacity())
+            .map_err(|_| Error::NoMemory)?;
+        let (front, back) = deque.as_slices();
+        if front.len() > fresh.capacity() || back.len() > fresh.capacity() - front.len() {
+            return Err(Error::NoMemory);
+        }
+        fresh.extend_from_slice(front);
+        fresh.extend_from_slice(back);
+        *deque = VecDeque::from(fresh);
+        Ok(())
+    }
+}
+
+impl<V: Storage> VecWriter<V> {
+    /// Creates a new `VecWriter<V>` that appends to `inner`.
+    pub const fn new(inner: V) -> VecWriter<V> {
+        VecWriter {
+            inner,
+            spare: 0,
+            reserved: 0,
+        }
+    }
+
+    /// Returns the number of bytes in the collection, including what was
+    /// there before.
+    pub fn len(&self) -> usize {
+        self.inner.len() - self.spare
+    }
+
+    /// Returns `true` if the collection is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Gets a mutable reference to the underlying collection.
+    ///
+    /// Any space reserved but not committed is released first.
+    pub fn get_mut(&mut self) -> &mut V {
+        self.release();
+        &mut self.inner
+    }
+
+    /// Unwraps this `VecWriter<V>`, returning the underlying collection.
+    pub fn into_inner(mut self) -> V {
+        self.release();
+        self.inner
+    }
+
+    fn release(&mut self) {
+        let len = self.len();
+        self.inner.truncate(len);
+        self.spare = 0;
+        self.reserved = 0;
+    }
+}
+
+impl<V: Storage> Write for VecWriter<V> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.reserved = 0;
+        if buf.len() <= self.spare {
+            self.inner.tail_mut(self.spare)?[..buf.len()].copy_from_slice(buf);
+            self.spare -= buf.len();
+            return Ok(buf.len());
+        }
+        self.release();
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<V: Storage> BufWrite for VecWriter<V> {
+    /// Returns the zeroed space past the written data, growing the collection
+    /// if less than `min` bytes are left.
+    ///
+    /// When growing, at most `max(min, DEFAULT_BUF_SIZE)` bytes are zeroed at
+    /// once, so that the cost does not depend on the capacity of the
+    /// collection.
+    fn reserve(&mut self, min: usize) -> Result<&mut [u8]> {
+        if self.spare < min {
+            let len = self.inner.len();
+            let needed = min - self.spare;
+            self.inner.try_reserve(needed)?;
+            let room = self.inner.capacity() - len;
+            // `try_reserve` made room for at least `needed` bytes.
+            let grow = cmp::min(room, cmp::max(needed, DEFAULT_BUF_SIZE));
+            self.inner.resize(len + grow);
+            self.spare += grow;
+        }
+        self.reserved = self.spare;
+        self.inner.tail_mut(self.spare)
+    }
+
+    /// Marks the first `n` bytes of the region returned by the most recent
+    /// [`reserve`] call as written. Does nothing if the region was already
+    /// committed, or something else was written since.
+    ///
+    /// [`reserve`]: BufWrite::reserve
+    fn commit(&mut self, n: usize) {
+        let n = cmp::min(n, self.reserved);
+        self.spare -= n;
+        self.reserved = 0;
+    }
+}
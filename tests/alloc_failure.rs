@@ -0,0 +1,239 @@
+//! Runs every growth path of the crate with an allocator that refuses to
+//! allocate, and checks that each one reports `NoMemory` instead of aborting.
+
+#![cfg(feature = "alloc")]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    collections::VecDeque,
+    ptr,
+};
+
+use axio::{prelude::*, *};
+
+/// Forwards to the system allocator, unless allocation failure is injected
+/// on the current thread.
+struct FailingAlloc;
+
+thread_local! {
+    static FAIL: Cell<bool> = const { Cell::new(false) };
+}
+
+fn failing() -> bool {
+    FAIL.try_with(Cell::get).unwrap_or(false)
+}
+
+unsafe impl GlobalAlloc for FailingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if failing() {
+            return ptr::null_mut();
+        }
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if failing() {
+            return ptr::null_mut();
+        }
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, p: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if failing() {
+            return ptr::null_mut();
+        }
+        unsafe { System.realloc(p, layout, new_size) }
+    }
+
+    unsafe fn dealloc(&self, p: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(p, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: FailingAlloc = FailingAlloc;
+
+/// Runs `f` with every allocation on this thread failing.
+fn without_memory<T>(f: impl FnOnce() -> T) -> T {
+    FAIL.with(|fail| fail.set(true));
+    let res = f();
+    FAIL.with(|fail| fail.set(false));
+    res
+}
+
+/// A reader that is not special-cased anywhere, so the default methods run.
+struct Plain<'a>(&'a [u8]);
+
+impl Read for Plain<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+/// A reader that returns at most 4 bytes at a time.
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let len = buf.len().min(4);
+        self.0.read(&mut buf[..len])
+    }
+}
+
+const DATA: &[u8] = b"first line\nsecond line\n";
+
+#[test]
+fn read_to_end() {
+    let mut buf = Vec::new();
+    let res = without_memory(|| Plain(DATA).read_to_end(&mut buf));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut src = DATA;
+    let res = without_memory(|| src.read_to_end(&mut buf));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut s = String::new();
+    let res = without_memory(|| Plain(DATA).read_to_string(&mut s));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut reader = BufReader::new(DATA);
+    reader.fill_buf().unwrap();
+    let res = without_memory(|| reader.read_to_end(&mut buf));
+    assert_eq!(res, Err(Error::NoMemory));
+    let res = without_memory(|| reader.read_to_string(&mut s));
+    assert_eq!(res, Err(Error::NoMemory));
+}
+
+#[test]
+fn read_until() {
+    let mut buf = Vec::new();
+    let mut s = String::new();
+    let mut src = DATA;
+    let res = without_memory(|| src.read_until(b'\n', &mut buf));
+    assert_eq!(res, Err(Error::NoMemory));
+    let res = without_memory(|| src.read_line(&mut s));
+    assert_eq!(res, Err(Error::NoMemory));
+    let res = without_memory(|| src.read_until_any(b"\n ", &mut buf));
+    assert_eq!(res, Err(Error::NoMemory));
+    let res = without_memory(|| src.read_until_seq(b"\ns", &mut buf));
+    assert_eq!(res, Err(Error::NoMemory));
+    let res = without_memory(|| src.read_until_limited(b'\n', &mut buf, 64));
+    assert_eq!(res, Err(Error::NoMemory));
+    let res = without_memory(|| src.read_line_limited(&mut s, 64));
+    assert_eq!(res, Err(Error::NoMemory));
+    let res = without_memory(|| src.read_line_lossy(&mut s));
+    assert_eq!(res, Err(Error::NoMemory));
+    // Nothing was consumed by the failed calls.
+    assert_eq!(src, DATA);
+}
+
+#[test]
+fn iterators() {
+    let res = without_memory(|| BufRead::split(DATA, b'\n').next());
+    assert_eq!(res, Some(Err(Error::NoMemory)));
+    let res = without_memory(|| DATA.split_seq(b"\ns").next());
+    assert_eq!(res, Some(Err(Error::NoMemory)));
+    let res = without_memory(|| DATA.lines().next());
+    assert_eq!(res, Some(Err(Error::NoMemory)));
+    let res = without_memory(|| DATA.lines_lossy().next());
+    assert_eq!(res, Some(Err(Error::NoMemory)));
+
+    // A record that spans two refills has to be copied.
+    let mut reader = BufReader::new(Trickle(DATA));
+    let res = without_memory(|| reader.for_each_split(b'\n', |_| Ok(())));
+    assert_eq!(res, Err(Error::NoMemory));
+    let mut reader = BufReader::new(Trickle(DATA));
+    let res = without_memory(|| reader.for_each_line(|_| Ok(())));
+    assert_eq!(res, Err(Error::NoMemory));
+}
+
+#[test]
+fn writers() {
+    let mut vec = Vec::new();
+    let res = without_memory(|| vec.write(DATA));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut deque = VecDeque::new();
+    let res = without_memory(|| deque.write(DATA));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut cursor = Cursor::new(Vec::new());
+    let res = without_memory(|| cursor.write(DATA));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut writer = VecWriter::new(Vec::new());
+    let res = without_memory(|| writer.reserve(DATA.len()).map(|b| b.len()));
+    assert_eq!(res, Err(Error::NoMemory));
+    let mut writer = VecWriter::new(VecDeque::new());
+    let res = without_memory(|| writer.reserve(DATA.len()).map(|b| b.len()));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut utf16 = Utf16Writer::new(Vec::new(), Utf16Endian::Little);
+    let res = without_memory(|| utf16.write_str("text"));
+    assert_eq!(res, Err(Error::NoMemory));
+}
+
+#[test]
+fn buffers() {
+    let res = without_memory(|| PrefetchReader::new(Device).map(|_| ()));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut reader = ReadAheadReader::new(DATA);
+    let res = without_memory(|| reader.fill_buf().map(|b| b.len()));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut rewind = Rewind::new(DATA, 16);
+    rewind.mark();
+    let res = without_memory(|| rewind.fill_buf().map(|b| b.len()));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut s = String::new();
+    let mut utf16 = Utf16Reader::with_endian(&b"t\0e\0x\0t\0"[..], Utf16Endian::Little);
+    let res = without_memory(|| utf16.read_to_string(&mut s));
+    assert_eq!(res, Err(Error::NoMemory));
+}
+
+#[test]
+fn collections() {
+    let mut chunks = ChunkList::new();
+    let res = without_memory(|| chunks.write(DATA));
+    assert_eq!(res, Err(Error::NoMemory));
+    let chunk = DATA.to_vec();
+    let res = without_memory(|| chunks.push_chunk(chunk));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut chunks = ChunkList::from(DATA.to_vec());
+    let res = without_memory(|| chunks.split_off(4).map(|_| ()));
+    assert_eq!(res, Err(Error::NoMemory));
+
+    let mut concat = ConcatSeek::new();
+    let res = without_memory(|| concat.push(DATA, DATA.len() as u64));
+    assert_eq!(res, Err(Error::NoMemory));
+}
+
+#[test]
+fn context() {
+    // The message is dropped, the rest of the error is kept.
+    let res: Result<()> = Err(Error::InvalidData);
+    let error = without_memory(|| res.with_offset(4).context("reading superblock"));
+    let error = error.unwrap_err();
+    assert_eq!(error.error(), Error::InvalidData);
+    assert_eq!(error.offset(), Some(4));
+    assert_eq!(error.messages().count(), 0);
+}
+
+/// A device that completes every read immediately.
+struct Device;
+
+impl StartRead for Device {
+    unsafe fn start_read(&mut self, _buf: &mut [u8]) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl CompleteRead for Device {
+    fn complete_read(&mut self) -> Result<usize> {
+        Ok(0)
+    }
+}
@@ -0,0 +1,85 @@
+//! Buffered readers on top of inner readers that misbehave.
+
+#![cfg(feature = "alloc")]
+
+use axio::{prelude::*, *};
+
+/// A reader whose `read_to_end` overwrites what is already in the buffer.
+struct Scribbler;
+
+impl Read for Scribbler {
+    fn read(&mut self, _buf: &mut [u8]) -> Result<usize> {
+        Ok(0)
+    }
+
+    fn read_to_end(&mut self, buf: &mut Vec<u8>) -> Result<usize> {
+        buf.fill(0xff);
+        buf.push(b'!');
+        Ok(1)
+    }
+}
+
+#[test]
+fn read_to_string_keeps_existing_contents() {
+    let mut s = String::from("text");
+    BufReader::new(Scribbler).read_to_string(&mut s).unwrap();
+    assert_eq!(s, "text!");
+
+    let mut s = String::from("text");
+    ReadAheadReader::new(Scribbler)
+        .read_to_string(&mut s)
+        .unwrap();
+    assert_eq!(s, "text!");
+}
+
+/// A reader that returns at most 16 bytes at a time, and counts how much
+/// buffer space it was offered.
+struct Trickle {
+    remaining: usize,
+    offered: usize,
+}
+
+impl Read for Trickle {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.offered += buf.len();
+        let n = buf.len().min(16).min(self.remaining);
+        buf[..n].fill(b'x');
+        self.remaining -= n;
+        Ok(n)
+    }
+}
+
+#[test]
+fn rewind_refills_are_bounded() {
+    let inner = Trickle {
+        remaining: 64 * 1024,
+        offered: 0,
+    };
+    let mut reader = Rewind::new(inner, 1024 * 1024);
+    reader.mark();
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf.len(), 64 * 1024);
+    // Each refill is offered at most one default-sized buffer.
+    assert!(reader.get_ref().offered <= (64 * 1024 / 16 + 1) * DEFAULT_BUF_SIZE);
+    reader.reset().unwrap();
+}
+
+#[test]
+fn rewind_peek_at_limit() {
+    let mut reader = Rewind::new(&b"0123456789"[..], 4);
+    reader.mark();
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf).unwrap();
+    // Looking past the window does not consume anything.
+    assert_eq!(reader.fill_buf().unwrap(), b"456789");
+    reader.reset().unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"0123");
+
+    reader.consume(1);
+    assert_eq!(reader.reset(), Err(Error::OutOfRange));
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, b"56789");
+}
@@ -0,0 +1,44 @@
+//! Newline conversion on top of writers that fail part way through.
+
+#![cfg(feature = "alloc")]
+
+use axio::{prelude::*, *};
+
+/// A writer that fails the first time it is handed an LF.
+#[derive(Default)]
+struct FailOnce {
+    out: Vec<u8>,
+    failed: bool,
+}
+
+impl Write for FailOnce {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if buf.starts_with(b"\n") && !self.failed {
+            self.failed = true;
+            return Err(Error::Interrupted);
+        }
+        self.out.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn retried_lf_is_not_doubled() {
+    let mut w = NewlineWriter::new(FailOnce::default());
+    assert!(w.write(b"\n").is_err());
+    assert_eq!(w.write(b"\n").unwrap(), 1);
+    assert_eq!(w.into_inner().out, b"\r\n");
+}
+
+#[test]
+fn abandoned_lf_does_not_eat_the_next_cr() {
+    let mut w = NewlineWriter::new(FailOnce::default());
+    assert!(w.write(b"\n").is_err());
+    w.write_all(b"x\n").unwrap();
+    assert_eq!(w.offset(), 2);
+    assert_eq!(w.into_inner().out, b"\rx\r\n");
+}
@@ -0,0 +1,207 @@
+//! A `no_std` program that calls the I/O traits with a reader and a writer
+//! that misbehave in every way the optimizer cannot rule out.
+//!
+//! Its panic handler calls a function that is not defined anywhere, so it
+//! only links if no panic is reachable. Build it with `--release`.
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use alloc::{collections::VecDeque, string::String, vec::Vec};
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    ffi::{c_int, c_void},
+    hint::black_box,
+    panic::PanicInfo,
+};
+
+use axio::{
+    BufReader, ChunkList, ConcatSeek, Cursor, Result, Rewind, Scanner, SeekFrom, Utf16Endian,
+    Utf16Reader, Utf16Writer, VecWriter, prelude::*,
+};
+
+#[link(name = "c")]
+unsafe extern "C" {
+    fn aligned_alloc(align: usize, size: usize) -> *mut c_void;
+    fn free(ptr: *mut c_void);
+}
+
+struct Malloc;
+
+unsafe impl GlobalAlloc for Malloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let align = layout.align().max(size_of::<usize>());
+        let size = layout.size().next_multiple_of(align);
+        unsafe { aligned_alloc(align, size).cast() }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, _: Layout) {
+        unsafe { free(ptr.cast()) }
+    }
+}
+
+#[global_allocator]
+static ALLOC: Malloc = Malloc;
+
+unsafe extern "C" {
+    /// Intentionally undefined.
+    fn axio_panic_is_reachable() -> !;
+}
+
+#[panic_handler]
+fn panic(_: &PanicInfo) -> ! {
+    unsafe { axio_panic_is_reachable() }
+}
+
+/// A reader, writer and stream that reports an arbitrary number of bytes
+/// transferred and an arbitrary position.
+struct Liar;
+
+impl Read for Liar {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        black_box(buf);
+        Ok(black_box(0))
+    }
+}
+
+impl Write for Liar {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        black_box(buf);
+        Ok(black_box(0))
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl BufRead for Liar {
+    fn fill_buf(&mut self) -> Result<&[u8]> {
+        Ok(black_box(b"1 2\n"))
+    }
+
+    fn consume(&mut self, amt: usize) {
+        black_box(amt);
+    }
+}
+
+impl Seek for Liar {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        black_box(pos);
+        Ok(black_box(0))
+    }
+}
+
+fn read() {
+    let mut buf = [0; 16];
+    let _ = black_box(Liar.read_exact(&mut buf));
+    let _ = black_box(Liar.read_exact_counted(&mut buf));
+    let _ = black_box(Liar.read_to_end_into(&mut buf));
+    let _ = black_box(Liar.read_to_string_into(&mut buf).map(str::len));
+
+    let mut reader = BufReader::new(Liar);
+    let _ = black_box(reader.fill_buf().map(<[u8]>::len));
+    reader.consume(black_box(usize::MAX));
+    let _ = black_box(reader.read(&mut buf));
+    let _ = black_box(reader.read_until_into(b'\n', &mut buf));
+    let _ = black_box(reader.read_line_into(&mut buf).map(str::len));
+    let _ = black_box(reader.skip_until(b'\n'));
+    let _ = black_box(reader.fill_buf_at_least(black_box(4)).map(<[u8]>::len));
+    let _ = black_box(reader.peek(black_box(4)).map(<[u8]>::len));
+
+    let data = black_box([0; 16]);
+    let mut slice = &data[..];
+    slice.consume(black_box(usize::MAX));
+    let _ = black_box(slice.read(&mut buf));
+
+    let mut cursor = Cursor::new(&data[..]);
+    let _ = black_box(cursor.seek(SeekFrom::Current(black_box(1))));
+    cursor.consume(black_box(usize::MAX));
+    let _ = black_box(cursor.read(&mut buf));
+
+    let mut reader = Utf16Reader::new(Liar);
+    let _ = black_box(reader.read_char());
+    let _ = black_box(reader.read_to_string(&mut String::new()));
+
+    let mut scanner = Scanner::new(Liar).with_comment(b'#');
+    let _ = black_box(scanner.next_token().map(|t| t.map(<[u8]>::len)));
+    let _ = black_box(scanner.next::<u32>());
+    let _ = black_box(scanner.next_u64_radix(black_box(16)));
+
+    let mut rewind = Rewind::new(Liar, black_box(8));
+    rewind.mark();
+    let _ = black_box(rewind.fill_buf().map(<[u8]>::len));
+    rewind.consume(black_box(usize::MAX));
+    let _ = black_box(rewind.read(&mut buf));
+    let _ = black_box(rewind.reset());
+    let _ = black_box(rewind.read_to_end_into(&mut buf));
+
+    let mut chunks = ChunkList::new();
+    let _ = black_box(chunks.push_chunk(Vec::new()));
+    let _ = black_box(chunks.seek(SeekFrom::End(black_box(-1))));
+    chunks.consume(black_box(usize::MAX));
+    chunks.advance(black_box(usize::MAX));
+    let _ = black_box(chunks.read(&mut buf));
+    let _ = black_box(chunks.fill_bufs().map(|[a, b]| a.len() + b.len()));
+    let _ = black_box(chunks.split_off(black_box(usize::MAX)).map(|c| c.len()));
+
+    let mut concat = ConcatSeek::new();
+    let _ = black_box(concat.push(Liar, black_box(u64::MAX)));
+    let _ = black_box(concat.push(Liar, black_box(4)));
+    let _ = black_box(concat.seek(SeekFrom::Current(black_box(-1))));
+    let _ = black_box(concat.read(&mut buf));
+    let _ = black_box(concat.size_hint());
+
+    let mut window = Liar.window(black_box(u64::MAX), black_box(u64::MAX));
+    let _ = black_box(window.seek(SeekFrom::End(black_box(1))));
+    let _ = black_box(window.read(&mut buf));
+    let _ = black_box(window.read_to_end_into(&mut buf));
+}
+
+fn write() {
+    let _ = black_box(Liar.write_all(b"data"));
+    let _ = black_box(Liar.write_all_counted(b"data"));
+    let _ = black_box(Liar.write_fmt(format_args!("{}", black_box(1))));
+
+    let mut buf = [0; 16];
+    let mut slice = &mut buf[..];
+    let _ = black_box(slice.write(b"data"));
+    if let Ok(space) = slice.reserve(black_box(1)) {
+        black_box(space);
+    }
+    slice.commit(black_box(usize::MAX));
+
+    let mut buf = [0; 16];
+    let mut cursor = Cursor::new(&mut buf[..]);
+    let _ = black_box(cursor.seek(SeekFrom::Start(black_box(1))));
+    let _ = black_box(cursor.write_all(b"data"));
+
+    let mut writer = Utf16Writer::new(Liar, Utf16Endian::Little);
+    let _ = black_box(writer.write_bom());
+    let _ = black_box(writer.write_str("data\u{10000}"));
+
+    let mut chunks = ChunkList::new();
+    let _ = black_box(chunks.write(b"data"));
+
+    let mut concat = ConcatSeek::new();
+    let _ = black_box(concat.push(Liar, black_box(4)));
+    let _ = black_box(concat.write(b"data"));
+
+    let mut writer = VecWriter::new(VecDeque::new());
+    let _ = black_box(writer.write(b"data"));
+    if let Ok(space) = writer.reserve(black_box(4)) {
+        black_box(space);
+    }
+    writer.commit(black_box(usize::MAX));
+    writer.commit(black_box(usize::MAX));
+    let _ = black_box(writer.into_inner().len());
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn main(_argc: c_int, _argv: *const *const u8) -> c_int {
+    read();
+    write();
+    0
+}
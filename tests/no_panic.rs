@@ -0,0 +1,23 @@
+//! Checks that no panic is reachable from the I/O traits with the `no-panic`
+//! feature, by building the program in `tests/no-panic` whose panic handler
+//! does not link.
+
+#![cfg(feature = "no-panic")]
+
+use std::process::Command;
+
+#[test]
+fn no_panic_is_reachable() {
+    let manifest = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/no-panic/Cargo.toml");
+    let output = Command::new(env!("CARGO"))
+        .args(["build", "--release", "--manifest-path", manifest])
+        .arg("--target-dir")
+        .arg(env!("CARGO_TARGET_TMPDIR"))
+        .output()
+        .expect("failed to run cargo");
+    assert!(
+        output.status.success(),
+        "a panic is reachable:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
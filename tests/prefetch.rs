@@ -0,0 +1,96 @@
+//! Drives a `PrefetchReader` with a device that writes its buffers while the
+//! reader is moved around. Run under Miri to check the buffers stay valid:
+//!
+//! ```text
+//! cargo +nightly miri test --features alloc --test prefetch
+//! ```
+
+#![cfg(feature = "alloc")]
+
+use std::ptr;
+
+use axio::{prelude::*, *};
+
+/// A device that only writes to the buffer when the read is completed, like
+/// a DMA transfer that finishes later.
+struct Device {
+    data: &'static [u8],
+    pending: Option<(*mut u8, usize)>,
+    started: usize,
+}
+
+impl Device {
+    fn new(data: &'static [u8]) -> Device {
+        Device {
+            data,
+            pending: None,
+            started: 0,
+        }
+    }
+}
+
+impl StartRead for Device {
+    unsafe fn start_read(&mut self, buf: &mut [u8]) -> Result<()> {
+        assert!(self.pending.is_none());
+        self.pending = Some((buf.as_mut_ptr(), buf.len()));
+        self.started += 1;
+        Ok(())
+    }
+}
+
+impl CompleteRead for Device {
+    fn complete_read(&mut self) -> Result<usize> {
+        let (dst, len) = self.pending.take().unwrap();
+        let n = len.min(self.data.len());
+        // SAFETY: the reader keeps the buffer alive and untouched until the
+        // read is completed.
+        unsafe { ptr::copy_nonoverlapping(self.data.as_ptr(), dst, n) };
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+const DATA: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+
+#[test]
+fn read_all() {
+    let mut reader = PrefetchReader::with_capacity(8, Device::new(DATA)).unwrap();
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).unwrap();
+    assert_eq!(out, DATA);
+    assert!(!reader.is_prefetching());
+}
+
+#[test]
+fn move_while_in_flight() {
+    let mut reader = PrefetchReader::with_capacity(8, Device::new(DATA)).unwrap();
+    reader.prefetch().unwrap();
+    assert!(reader.is_prefetching());
+
+    // Moving the reader must not invalidate the buffer the device writes to.
+    let mut reader = Box::new(reader);
+    assert_eq!(reader.fill_buf().unwrap(), b"01234567");
+    assert!(reader.is_prefetching());
+    let reader = *reader;
+    let mut readers = vec![reader];
+    let mut reader = readers.pop().unwrap();
+
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).unwrap();
+    assert_eq!(out, DATA);
+}
+
+#[test]
+fn drop_while_in_flight() {
+    let mut reader = PrefetchReader::with_capacity(8, Device::new(DATA)).unwrap();
+    reader.fill_buf().unwrap();
+    assert!(reader.is_prefetching());
+    let moved = reader;
+    drop(moved);
+
+    let mut reader = PrefetchReader::with_capacity(8, Device::new(DATA)).unwrap();
+    reader.fill_buf().unwrap();
+    let device = reader.into_inner();
+    assert_eq!(device.started, 2);
+    assert!(device.pending.is_none());
+}
@@ -0,0 +1,44 @@
+//! Readers whose size hint is looser than what they actually hold.
+
+#![cfg(feature = "alloc")]
+
+use axio::{prelude::*, *};
+
+/// A reader that claims it may hold far more than it does.
+struct Boaster(&'static [u8]);
+
+impl Read for Boaster {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.0.read(buf)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(usize::MAX / 2))
+    }
+}
+
+#[test]
+fn read_to_end_with_huge_upper_bound() {
+    let mut buf = Vec::new();
+    Boaster(b"hello").read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"hello");
+
+    let mut buf = Vec::new();
+    BufReader::new(Boaster(b"hello"))
+        .read_to_end(&mut buf)
+        .unwrap();
+    assert_eq!(buf, b"hello");
+}
+
+#[test]
+fn read_to_string_with_huge_upper_bound() {
+    let mut s = String::new();
+    Boaster(b"hello").read_to_string(&mut s).unwrap();
+    assert_eq!(s, "hello");
+
+    let mut s = String::new();
+    BufReader::new(Boaster(b"hello"))
+        .read_to_string(&mut s)
+        .unwrap();
+    assert_eq!(s, "hello");
+}
@@ -0,0 +1,69 @@
+//! Writing into `Vec<u8>` and `VecDeque<u8>` through `BufWrite`.
+
+#![cfg(feature = "alloc")]
+
+use std::collections::VecDeque;
+
+use axio::{prelude::*, *};
+
+#[test]
+fn commit_without_reserve() {
+    let mut writer = VecWriter::new(b"hello world".to_vec());
+    writer.commit(0);
+    writer.commit(5);
+    assert_eq!(writer.into_inner(), b"hello world");
+}
+
+#[test]
+fn abandoned_reserve() {
+    let mut writer = VecWriter::new(b"abc".to_vec());
+    writer.reserve(4).unwrap()[..2].copy_from_slice(b"de");
+    assert_eq!(writer.len(), 3);
+    assert_eq!(writer.get_mut(), b"abc");
+
+    writer.reserve(4).unwrap()[..2].copy_from_slice(b"de");
+    writer.commit(2);
+    // A second commit has no region to mark.
+    writer.commit(2);
+    writer.write_all(b"f").unwrap();
+    assert_eq!(writer.into_inner(), b"abcdef");
+}
+
+#[test]
+fn reserve_reuses_space() {
+    let mut writer = VecWriter::new(Vec::new());
+    let len = writer.reserve(1).unwrap().len();
+    assert!(len >= 1);
+    writer.commit(1);
+    assert_eq!(writer.reserve(1).unwrap().len(), len - 1);
+}
+
+#[test]
+fn ring_buffer() {
+    let mut deque = VecDeque::with_capacity(8);
+    deque.extend(b"xxxxxxab");
+    deque.drain(..6);
+    let mut writer = VecWriter::new(deque);
+    for chunk in [&b"cdef"[..], b"ghijklmnop"] {
+        let region = writer.reserve(chunk.len()).unwrap();
+        region[..chunk.len()].copy_from_slice(chunk);
+        writer.commit(chunk.len());
+    }
+    let deque = writer.into_inner();
+    assert!(deque.iter().eq(b"abcdefghijklmnop"));
+}
+
+#[test]
+fn copy_into() {
+    let data: Vec<u8> = (0..10_000u32).map(|i| i as u8).collect();
+
+    let mut writer = VecWriter::new(b"<".to_vec());
+    assert_eq!(copy_buf(&mut &data[..], &mut writer).unwrap(), 10_000);
+    let vec = writer.into_inner();
+    assert_eq!(vec[0], b'<');
+    assert_eq!(&vec[1..], data);
+
+    let mut writer = VecWriter::new(VecDeque::new());
+    copy_buf(&mut &data[..], &mut writer).unwrap();
+    assert!(writer.into_inner().iter().eq(&data));
+}
@@ -0,0 +1,17 @@
+//! Checks that `ReadAheadReader`'s accessors do not clash with
+//! [`Seek::window`] when the prelude is imported.
+
+#![cfg(feature = "alloc")]
+
+use axio::{prelude::*, *};
+
+#[test]
+fn read_ahead_window() {
+    let reader = ReadAheadReader::new(Cursor::new(b"0123456789"));
+    assert!(reader.current_window() > 0);
+
+    let mut window = reader.window(2, 4);
+    let mut buf = Vec::new();
+    window.read_to_end(&mut buf).unwrap();
+    assert_eq!(buf, b"2345");
+}